@@ -0,0 +1,193 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use crossbeam_channel::unbounded;
+use futures_core::Stream;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use crate::IbClient;
+use crate::client_socket::{self, ClientSocket, ReplayRequests, Subscriptions};
+use crate::decoder::{Decoder, TimeZones};
+use crate::constants::helper_constants;
+use crate::enums::incoming_message_enum::IncomingMessagesEnum;
+use crate::models::contract::Contract;
+use crate::models::order::Order;
+use crate::models::scanner_subscription::ScannerSubscription;
+use crate::models::tag_value::TagValue;
+use crate::models::execution_filter::ExecutionFilter;
+use crate::enums::account_summary_tag::AccountSummaryTag;
+use crate::enums::exercise_action::ExerciseAction;
+use crate::enums::subscription::Subscription;
+
+// Each request is encoded and validated by an IbClient that never owns a socket, the resulting
+// buffers are then written to the tokio stream.
+macro_rules! async_request {
+    ($name:ident($($arg:ident: $arg_type:ty),*)) => {
+        #[allow(clippy::too_many_arguments)]
+        pub async fn $name(&mut self, $($arg: $arg_type),*) -> io::Result<()> {
+            self.inner.$name($($arg),*);
+            self.flush().await
+        }
+    };
+}
+
+pub struct AsyncIbClient {
+    inner: IbClient,
+    writer: OwnedWriteHalf,
+    event_sender: UnboundedSender<IncomingMessagesEnum>,
+}
+
+pub struct EventStream {
+    receiver: UnboundedReceiver<IncomingMessagesEnum>,
+}
+
+impl EventStream {
+    pub async fn recv(&mut self) -> Option<IncomingMessagesEnum> {
+        self.receiver.recv().await
+    }
+}
+
+impl Stream for EventStream {
+    type Item = IncomingMessagesEnum;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl AsyncIbClient {
+    pub async fn connect(host: &str, port: i32, client_id: i32) -> io::Result<(AsyncIbClient, EventStream)> {
+        let mut tcp_stream = TcpStream::connect(format!("{}:{}", host, port)).await?;
+
+        tcp_stream.write_all(ClientSocket::connect_request_buffer()?.as_slice()).await?;
+        let msg = read_message(&mut tcp_stream).await?;
+        let (server_version, _server_time) = ClientSocket::read_connect_ack(msg)?;
+        tcp_stream.write_all(ClientSocket::start_api_buffer(server_version, client_id)?.as_slice()).await?;
+
+        let (reader, writer) = tcp_stream.into_split();
+        let (event_sender, receiver) = mpsc::unbounded_channel();
+
+        let inner = IbClient::new_buffered(client_id, server_version);
+        let socket = &inner.client_socket;
+        tokio::spawn(read_messages(reader, server_version, socket.time_zones.clone(), socket.subscriptions.clone(), socket.replay_requests.clone(), event_sender.clone()));
+
+        let client = AsyncIbClient {
+            inner,
+            writer,
+            event_sender,
+        };
+
+        Ok((client, EventStream { receiver }))
+    }
+
+    async_request!(req_market_data(req_id: i32, contract: &Contract, generic_tick_list: &str, snapshot: bool, regulatory_snapshot: bool, market_data_options: Vec<TagValue>));
+    async_request!(cancel_market_data(req_id: i32));
+    async_request!(req_scanner_subscription(req_id: i32, subscription: ScannerSubscription));
+    async_request!(req_account_summary(req_id: i32, group: &str, tags: &[AccountSummaryTag]));
+    async_request!(cancel_account_summary(req_id: i32));
+    async_request!(req_contract_details(req_id: i32, contract: &Contract));
+    async_request!(req_open_orders());
+    async_request!(req_all_open_orders());
+    async_request!(req_auto_open_orders(auto_bind: bool));
+    async_request!(req_ids(num_ids: i32));
+    async_request!(req_global_cancel());
+    async_request!(req_cancel_order(req_id: i32, order_id: i32));
+    async_request!(req_completed_orders(api_only: bool));
+    async_request!(req_historical_data(req_id: i32, contract: Contract, end_date_time: &str, duration: &str, bar_size_setting: &str, what_to_show: &str, use_rth: i32, date_format: i32, keep_up_to_date: bool, chart_options: Vec<TagValue>));
+    async_request!(cancel_historical_data(req_id: i32));
+    async_request!(req_historical_ticks(req_id: i32, contract: &Contract, start_date_time: &str, end_date_time: &str, number_of_ticks: i32, what_to_show: &str, use_rth: i32, ignore_size: bool, misc_options: Vec<TagValue>));
+    async_request!(req_head_timestamp(req_id: i32, contract: &Contract, what_to_show: &str, use_rth: i32, format_date: i32));
+    async_request!(cancel_head_timestamp(req_id: i32));
+    async_request!(req_histogram_data(req_id: i32, contract: &Contract, use_rth: bool, time_period: &str));
+    async_request!(cancel_histogram_data(req_id: i32));
+    async_request!(req_real_time_bars(req_id: i32, contract: Contract, bar_size: i32, what_to_show: &str, use_rth: bool, real_time_bar_options: Vec<TagValue>));
+    async_request!(cancel_real_time_bars(req_id: i32));
+    async_request!(req_tick_by_tick(req_id: i32, contract: Contract, tick_type: &str, number_of_ticks: i32, ignore_size: bool));
+    async_request!(req_pnl(req_id: i32, account: &str, model_code: &str));
+    async_request!(cancel_pnl(req_id: i32));
+    async_request!(req_pnl_single(req_id: i32, account: &str, model_code: &str, con_id: i32));
+    async_request!(cancel_pnl_single(req_id: i32));
+    async_request!(cancel_scanner_subscription(req_id: i32));
+    async_request!(cancel_tick_subscription(req_id: i32));
+    async_request!(req_market_depth(req_id: i32, contract: &Contract, num_rows: i32, is_smart_depth: bool, mkt_depth_options: Vec<TagValue>));
+    async_request!(cancel_market_depth(req_id: i32, is_smart_depth: bool));
+    async_request!(req_executions(req_id: i32, filter: &ExecutionFilter));
+    async_request!(req_positions());
+    async_request!(cancel_positions());
+    async_request!(req_account_updates(subscribe: bool, account: &str));
+    async_request!(req_sec_def_opt_params(req_id: i32, underlying_symbol: &str, fut_fop_exchange: &str, underlying_sec_type: &str, underlying_con_id: i32));
+    async_request!(calculate_implied_volatility(req_id: i32, contract: &Contract, option_price: f64, under_price: f64, implied_volatility_options: Vec<TagValue>));
+    async_request!(cancel_calculate_implied_volatility(req_id: i32));
+    async_request!(calculate_option_price(req_id: i32, contract: &Contract, volatility: f64, under_price: f64, option_price_options: Vec<TagValue>));
+    async_request!(cancel_calculate_option_price(req_id: i32));
+    async_request!(exercise_options(req_id: i32, contract: &Contract, action: ExerciseAction, quantity: i32, account: &str, override_natural_action: bool));
+    async_request!(cancel(req_id: i32));
+    async_request!(place_order(order_id: i32, contract: Contract, order: Order));
+
+    pub fn set_time_zone(&mut self, req_id: i32, time_zone_id: &str) {
+        self.inner.set_time_zone(req_id, time_zone_id);
+    }
+
+    pub fn clear_time_zone(&mut self, req_id: i32) {
+        self.inner.clear_time_zone(req_id);
+    }
+
+    pub fn subscriptions(&self) -> Vec<(i32, Subscription)> {
+        self.inner.subscriptions()
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        // validation errors are reported by the inner client, hand them to the stream as well
+        for event in self.inner.event_receiver.try_iter() {
+            self.event_sender.send(event).unwrap_or_default();
+        }
+
+        for buffer in self.inner.take_outgoing() {
+            self.writer.write_all(buffer.as_slice()).await?;
+        }
+
+        self.writer.flush().await
+    }
+}
+
+async fn read_message<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let msg_size = reader.read_i32().await?;
+
+    if !(0..=helper_constants::MAX_MSG_SIZE).contains(&msg_size) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Bad TCP Message Length {}", msg_size)));
+    }
+
+    let mut msg = vec![0_u8; msg_size as usize];
+    reader.read_exact(&mut msg).await?;
+    Ok(msg)
+}
+
+async fn read_messages(mut reader: OwnedReadHalf, server_version: i32, time_zones: TimeZones, subscriptions: Subscriptions, replay_requests: ReplayRequests, event_sender: UnboundedSender<IncomingMessagesEnum>) {
+    let (decoder_sender, decoder_receiver) = unbounded();
+
+    loop {
+        let msg = match read_message(&mut reader).await {
+            Ok(msg) => msg,
+            Err(err) => {
+                eprintln!("ib_rust_api async reader stopped: {}", err);
+                event_sender.send(IncomingMessagesEnum::Disconnected).unwrap_or_default();
+                return;
+            }
+        };
+
+        let fields = ClientSocket::read_fields(String::from_utf8_lossy(msg.as_slice()).as_ref());
+        let mut decoder = Decoder::new(fields.as_slice()).with_time_zones(&time_zones);
+        decoder.process_incoming_message(server_version, &decoder_sender).unwrap_or_else(|err| {
+            eprintln!("ib_rust_api error process_incoming_message: {}", err);
+        });
+
+        for event in decoder_receiver.try_iter() {
+            client_socket::end_failed_stream(&event, &subscriptions, &replay_requests);
+            if event_sender.send(event).is_err() {
+                return;
+            }
+        }
+    }
+}
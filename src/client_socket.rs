@@ -1,26 +1,50 @@
 use std::net::{TcpStream, Shutdown};
+use crate::frame_reader::FrameReader;
 use crate::api_parameter::ApiParameters;
 use std::io::{Write, Read, Seek, SeekFrom, Cursor, ErrorKind};
-use crate::decoder::Decoder;
+use crate::decoder::{Decoder, TimeZones};
 use std::{thread, io};
 use std::error::Error;
 use crossbeam_channel::{unbounded, Sender, Receiver};
 use crate::enums::incoming_message_enum::IncomingMessagesEnum;
 use crate::enums::outgoing_messages::OutgoingMessages;
+use crate::enums::subscription::Subscription;
 use crate::constants::{min_server_version, helper_constants};
-use std::convert::TryInto;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::Duration;
+
+// encoded requests to send again after a reconnect, keyed by (message type, req_id)
+pub(crate) type ReplayRequests = Arc<Mutex<HashMap<(i32, i32), Vec<u8>>>>;
+// streams started and not ended yet, keyed like the replay requests
+pub(crate) type Subscriptions = Arc<Mutex<HashMap<(i32, i32), Subscription>>>;
 
 pub struct ClientSocket {
     pub host: String,
     pub port: i32,
     pub client_id: i32,
-    pub tcp_stream: Option<TcpStream>,
-    pub server_version: i32,
+    pub tcp_stream: Arc<Mutex<Option<TcpStream>>>,
+    // shared with the decoder thread and the reconnector, which updates it for a new session
+    server_version: Arc<AtomicI32>,
     pub is_connected: bool,
     pub extra_auth: bool,
     pub use_v1000_plus: bool,
     pub server_time: String,
     event_sender: Sender<IncomingMessagesEnum>,
+    pub(crate) replay_requests: ReplayRequests,
+    pub(crate) subscriptions: Subscriptions,
+    pub(crate) time_zones: TimeZones,
+    outbox: Option<Vec<Vec<u8>>>,
+}
+
+struct Reconnector {
+    address: String,
+    client_id: i32,
+    server_version: Arc<AtomicI32>,
+    tcp_stream: Arc<Mutex<Option<TcpStream>>>,
+    replay_requests: ReplayRequests,
+    event_sender: Sender<IncomingMessagesEnum>,
 }
 
 impl ClientSocket {
@@ -29,22 +53,71 @@ impl ClientSocket {
             host,
             port,
             client_id,
-            tcp_stream: None,
-            server_version: 0,
+            tcp_stream: Arc::new(Mutex::new(None)),
+            server_version: Arc::new(AtomicI32::new(0)),
             is_connected: false,
             extra_auth: false,
             use_v1000_plus: true,
             server_time: "".to_string(),
             event_sender,
+            replay_requests: Arc::new(Mutex::new(HashMap::new())),
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            time_zones: Arc::new(Mutex::new(HashMap::new())),
+            outbox: None,
         }
     }
 
+    // A socket that is already past the handshake and queues encoded requests instead of writing
+    // them, so another transport can pick them up with take_outbox.
+    #[cfg(feature = "async")]
+    pub(crate) fn new_buffered(client_id: i32, server_version: i32, event_sender: Sender<IncomingMessagesEnum>) -> Self {
+        let mut client_socket = ClientSocket::new("".to_string(), 0, client_id, event_sender);
+        client_socket.set_server_version(server_version);
+        client_socket.is_connected = true;
+        client_socket.outbox = Some(vec![]);
+        client_socket
+    }
+
+    pub fn server_version(&self) -> i32 {
+        self.server_version.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn set_server_version(&mut self, server_version: i32) {
+        self.server_version.store(server_version, Ordering::SeqCst);
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn take_outbox(&mut self) -> Vec<Vec<u8>> {
+        self.outbox.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
     pub fn connect(&mut self) -> Result<(), Box<dyn Error>> {
+        if !self.use_v1000_plus {
+            unimplemented!()
+        }
+
         let url = format!("{}:{}", self.host, self.port);
-        match TcpStream::connect(url) {
+        let (tcp_stream, server_version, server_time) = ClientSocket::open_session(url.as_str(), self.client_id)?;
+
+        self.set_server_version(server_version);
+        self.server_time = server_time;
+        self.is_connected = true;
+        *self.tcp_stream.lock().unwrap() = Some(tcp_stream);
+
+        let (tx, rx) = unbounded();
+
+        self.listen_for_messages(tx)?;
+
+        self.parse_messages(rx)?;
+
+        Ok(())
+    }
+
+    fn open_session(url: &str, client_id: i32) -> Result<(TcpStream, i32, String), Box<dyn Error>> {
+        let mut tcp_stream = match TcpStream::connect(url) {
             Ok(res) => {
-                self.tcp_stream = Some(res);
                 println!("Connected to the server");
+                res
             },
             Err(e) => {
                 println!("Couldn't connect to server...");
@@ -52,209 +125,191 @@ impl ClientSocket {
                     Box::new(std::io::Error::new(ErrorKind::NotConnected, e))
                 );
             }
-        }
+        };
 
-        self.send_connect_request()?;
+        let (server_version, server_time) = ClientSocket::send_connect_request(&mut tcp_stream)?;
 
-        if self.is_connected {
-            self.tell_ib_start_api()?;
-        }
+        ClientSocket::tell_ib_start_api(&mut tcp_stream, server_version, client_id)?;
 
-        let (tx, rx) = unbounded();
+        Ok((tcp_stream, server_version, server_time))
+    }
 
-        self.listen_for_messages(tx)?;
+    fn send_connect_request(tcp_stream: &mut TcpStream) -> Result<(i32, String), Box<dyn Error>> {
+        ClientSocket::write_buffer(tcp_stream, ClientSocket::connect_request_buffer()?.as_slice())?;
 
-        self.parse_messages(rx)?;
+        let msg = ClientSocket::read_single_message(tcp_stream)?;
 
-        Ok(())
+        let (server_version, server_time) = ClientSocket::read_connect_ack(msg)?;
+        Ok((server_version, server_time))
     }
 
-    fn send_connect_request(&mut self) -> std::io::Result<()> {
-        if self.use_v1000_plus {
-            let mut params = ApiParameters::new();
-            params.add_string("API");
+    pub(crate) fn connect_request_buffer() -> io::Result<Vec<u8>> {
+        let mut params = ApiParameters::new();
+        params.add_string("API");
 
-            let length_pos = params.prepare_buffer(self.use_v1000_plus);
+        let length_pos = params.prepare_buffer(true);
 
-            params.add_string_without_eol("v100..151");
+        params.add_string_without_eol("v100..151");
 
-            self.close_and_send(&mut params, length_pos).unwrap_or_else(|err| {
-                std::io::Error::new(ErrorKind::Other, format!("Error client_socket, send_connect_request: {}", err.to_string()));
-            });
+        Ok(ClientSocket::close_buffer(&mut params, length_pos, true)?.to_vec())
+    }
 
-            if let Some(tcp_stream) = self.tcp_stream.as_mut() {
-                let msg = ClientSocket::read_single_message(tcp_stream)?;
+    pub(crate) fn read_connect_ack(msg: Vec<u8>) -> io::Result<(i32, String)> {
+        let mut server_version = 0;
+        let mut server_time = "".to_string();
+        let mut is_connected = false;
+        Decoder::process_connect_ack(Cursor::new(msg), &mut server_version, &mut server_time, &mut is_connected);
 
-                Decoder::process_connect_ack(Cursor::new(msg), &mut self.server_version, &mut self.server_time, &mut self.is_connected);
-            }
-        }
-        else {
-            unimplemented!()
+        if !is_connected {
+            return Err(io::Error::new(ErrorKind::NotConnected, "Connect request was not acknowledged"));
         }
 
-        Ok(())
+        Ok((server_version, server_time))
     }
 
     fn read_single_message(tcp_stream: &mut TcpStream) -> Result<Vec<u8>, std::io::Error> {
-        let msg_size = ClientSocket::read_tcp_i32(tcp_stream)?;
+        let mut size_buf = [0_u8; 4];
+        tcp_stream.read_exact(&mut size_buf)?;
+        let msg_size = i32::from_be_bytes(size_buf);
 
-        if msg_size > helper_constants::MAX_MSG_SIZE {
-            //TODO log
-            eprintln!("Bad TCP Message Length");
-            return Ok(vec![]);
+        if !(0..=helper_constants::MAX_MSG_SIZE).contains(&msg_size) {
+            return Err(io::Error::new(ErrorKind::InvalidData, format!("Bad TCP Message Length {}", msg_size)));
         }
 
-        let msg = ClientSocket::read_tcp_byte_array(tcp_stream, msg_size)?;
+        let mut msg = vec![0_u8; msg_size as usize];
+        tcp_stream.read_exact(&mut msg)?;
         Ok(msg)
     }
 
-    pub fn close_and_send(&mut self, params: &mut ApiParameters, length_pos: u32) -> Result<(), Box<dyn Error>> { //make return Result and if error use
-        if self.use_v1000_plus {
-            params.cursor.seek(SeekFrom::Start(length_pos as u64))?;
-            let value = (params.cursor.get_ref().len() - length_pos as usize - std::mem::size_of::<i32>()) as i32;
-            params.cursor.write(&value.to_be_bytes())?;
-        }
-
-        match self.tcp_stream.as_mut() {
-            Some(tcp) => {
-                let bytes_written = tcp.write(params.cursor.get_ref())?;
-
-                let buffer_len = params.cursor.get_ref().len();
+    pub fn close_and_send(&mut self, params: &mut ApiParameters, length_pos: u32) -> Result<(), Box<dyn Error>> {
+        let buffer = ClientSocket::close_buffer(params, length_pos, self.use_v1000_plus)?;
+        self.send_buffer(buffer)
+    }
 
-                if bytes_written < buffer_len {
-                    return Err(Box::new(io::Error::new(io::ErrorKind::Interrupted, format!("Sent {}/{} bytes", bytes_written, buffer_len))));
-                }
+    // Same as close_and_send, but once sent the request is kept and sent again after an automatic
+    // reconnect until forget_replayable is called for its request type and req_id. A buffered
+    // socket never reconnects, so nothing is kept there.
+    pub fn close_and_send_replayable(&mut self, request: OutgoingMessages, req_id: i32, params: &mut ApiParameters, length_pos: u32) -> Result<(), Box<dyn Error>> {
+        let buffer = ClientSocket::close_buffer(params, length_pos, self.use_v1000_plus)?;
 
-                tcp.flush()?;
-            },
-            None => {
-                return Err(Box::new(io::Error::new(io::ErrorKind::Interrupted, "Error retrieving tcp stream".to_string())));
-            }
+        if self.outbox.is_some() {
+            return self.send_buffer(buffer);
         }
 
+        let replay_requests = self.replay_requests.clone();
+        let mut replay_requests = replay_requests.lock().unwrap();
+        self.send_buffer(buffer)?;
+        replay_requests.insert((request as i32, req_id), buffer.to_vec());
+
         Ok(())
     }
 
-    fn read_tcp_i32(tcp_stream: &mut TcpStream) -> Result<i32, std::io::Error> {
-        let mut read_buf = [0u8; 4];
-        tcp_stream.read(&mut read_buf)?;
-        Ok(i32::from_be_bytes(read_buf))
+    pub(crate) fn track_subscription(&mut self, request: OutgoingMessages, req_id: i32, subscription: Subscription) {
+        self.subscriptions.lock().unwrap().insert((request as i32, req_id), subscription);
     }
 
-    fn read_tcp_byte_array(tcp_stream: &mut TcpStream, msg_size: i32) -> Result<Vec<u8>, std::io::Error> {
-        let mut buf = vec![0_u8; msg_size as usize];
-        tcp_stream.read(&mut buf)?;
-        Ok(buf)
+    // Called when a stream is cancelled, it is neither replayed nor tracked any more.
+    pub fn forget_replayable(&mut self, request: OutgoingMessages, req_id: i32) {
+        let key = (request as i32, req_id);
+        self.replay_requests.lock().unwrap().remove(&key);
+        self.subscriptions.lock().unwrap().remove(&key);
     }
 
-    fn tell_ib_start_api(&mut self) -> Result<(), Box<dyn Error>> {
-//        if self.check_connection() == false {
-//            return
-//        }
-
-        const VERSION: i32 = 2;
-
-        let mut params = ApiParameters::new();
-        let length_pos = params.prepare_buffer(self.use_v1000_plus);
-        params.add_int(OutgoingMessages::StartApi as i32);
-        params.add_int(VERSION);
-        params.add_int(self.client_id);
-
-        if self.server_version > min_server_version::OPTIONAL_CAPABILITIES {
-            let optional_capabilities = "";
-            params.add_string(optional_capabilities);
+    fn close_buffer(params: &mut ApiParameters, length_pos: u32, use_v1000_plus: bool) -> io::Result<&[u8]> {
+        if use_v1000_plus {
+            params.cursor.seek(SeekFrom::Start(length_pos as u64))?;
+            let value = (params.cursor.get_ref().len() - length_pos as usize - std::mem::size_of::<i32>()) as i32;
+            params.cursor.write_all(&value.to_be_bytes())?;
         }
 
-        Ok(self.close_and_send(&mut params, length_pos)?)
+        Ok(params.cursor.get_ref())
     }
 
-    fn recv_all_msg(tcp_stream: &mut TcpStream) -> Result<Vec<u8>, Box<dyn Error>> {
-        let mut cont = true;
-        let mut allbuf: Vec<u8> = Vec::new();
-        const NUM_BYTES: usize = 4096;
-
-        while cont {
-            let mut buf: [u8; NUM_BYTES] = [0; NUM_BYTES];
-
-            let bytes_read = tcp_stream.read(&mut buf).expect("Couldnt read from reader...");
-
-            allbuf.extend_from_slice(&buf[0..bytes_read]);
-            //logger.debug("len %d raw:%s|", len(buf), buf)
+    fn send_buffer(&mut self, buffer: &[u8]) -> Result<(), Box<dyn Error>> {
+        if let Some(outbox) = self.outbox.as_mut() {
+            outbox.push(buffer.to_vec());
+            return Ok(());
+        }
 
-            if bytes_read < NUM_BYTES {
-                cont = false;
+        match self.tcp_stream.lock().unwrap().as_mut() {
+            Some(tcp) => ClientSocket::write_buffer(tcp, buffer),
+            None => {
+                Err(Box::new(io::Error::new(io::ErrorKind::Interrupted, "Error retrieving tcp stream".to_string())))
             }
         }
-        Ok(allbuf)
     }
 
-    pub fn recv_packet(tcp_stream :&mut TcpStream) -> Result<Vec<u8>, Box<dyn Error>> {
-        let buf = Self::recv_all_msg(tcp_stream)?;
-        // receiving 0 bytes outside a timeout means the connection is either
-        // closed or broken
-        if buf.len() == 0 {
-            tcp_stream.shutdown(Shutdown::Both)?;
-            eprintln!("IbRustApi Error: Cannot connect - socket either closed or broken, disconnecting");
+    fn write_buffer(tcp: &mut TcpStream, buffer: &[u8]) -> Result<(), Box<dyn Error>> {
+        let bytes_written = tcp.write(buffer)?;
+
+        if bytes_written < buffer.len() {
+            return Err(Box::new(io::Error::new(io::ErrorKind::Interrupted, format!("Sent {}/{} bytes", bytes_written, buffer.len()))));
         }
-        Ok(buf)
+
+        tcp.flush()?;
+
+        Ok(())
     }
 
-    pub fn read_msg<'a>(buf: &[u8]) -> Result<(usize, String, Vec<u8>), Box<dyn Error>> {
-        // first the size prefix and then the corresponding msg payload ""
+    fn tell_ib_start_api(tcp_stream: &mut TcpStream, server_version: i32, client_id: i32) -> Result<(), Box<dyn Error>> {
+        ClientSocket::write_buffer(tcp_stream, ClientSocket::start_api_buffer(server_version, client_id)?.as_slice())
+    }
 
-        if buf.len() < 4 {
-            dbg!("read_msg:  buffer too small!! {:?}", buf.len());
-            return Ok((0, String::new(), buf.to_vec()));
-        }
+    pub(crate) fn start_api_buffer(server_version: i32, client_id: i32) -> io::Result<Vec<u8>> {
+        const VERSION: i32 = 2;
 
-        let size = i32::from_be_bytes(buf[0..4].try_into().unwrap()) as usize;
-        //debug!("read_msg: Message size: {:?}", size);
+        let mut params = ApiParameters::new();
+        let length_pos = params.prepare_buffer(true);
+        params.add_int(OutgoingMessages::StartApi as i32);
+        params.add_int(VERSION);
+        params.add_int(client_id);
 
-        if buf.len() - 4 >= size {
-            let text = String::from_utf8(buf[4..4 + size].to_vec()).unwrap();
-            //debug!("read_msg: text in read message: {:?}", text);
-            Ok((size, text, buf[4 + size..].to_vec()))
-        } else {
-            Ok((size, String::new(), buf.to_vec()))
+        if server_version > min_server_version::OPTIONAL_CAPABILITIES {
+            let optional_capabilities = "";
+            params.add_string(optional_capabilities);
         }
+
+        Ok(ClientSocket::close_buffer(&mut params, length_pos, true)?.to_vec())
     }
 
     fn listen_for_messages(&mut self, messages: Sender<String>) -> Result<(), Box<dyn Error>> {
-        let tcp = self.tcp_stream.as_ref();
-        if let Some(tcp) = tcp {
-            let mut tcp_clone = tcp.try_clone()?;
-            thread::spawn(move || {
-                loop {
-                    // grab a packet of messages from the socket
-                    let mut message_packet = Self::recv_packet(&mut tcp_clone).unwrap();
-                    //debug!(" recvd size {}", message_packet.len());
-
-                    // Read messages from the packet until there are no more.
-                    // When this loop ends, break into the outer loop and grab another packet.
-                    // Repeat until the connection is closed
-                    let _msg = String::new();
-                    while message_packet.len() > 0 {
-                        // Read a message from the packet then add it to the message queue below.
-                        let (_size, msg, remaining_messages) = Self::read_msg(message_packet.as_slice()).unwrap();
-
-                        // clear the Vec that holds the bytes from the packet
-                        // and reload with the bytes that haven't been read.
-                        // The variable remaining_messages only holds the unread messagesleft in the packet
-                        message_packet.clear();
-                        message_packet.extend_from_slice(remaining_messages.as_slice());
-
-                        if msg.as_str() != "" {
-                            messages.send(msg).expect("READER CANNOT SEND MESSAGE");
-                        } else {
-                            //Break to the outer loop in run and get another packet of messages.
-
-                            dbg!("more incoming packet(s) are needed ");
-                            break;
-                        }
+        let tcp_clone = match self.tcp_stream.lock().unwrap().as_ref() {
+            Some(tcp) => tcp.try_clone()?,
+            None => return Ok(())
+        };
+
+        let reconnector = Reconnector {
+            address: format!("{}:{}", self.host, self.port),
+            client_id: self.client_id,
+            server_version: self.server_version.clone(),
+            tcp_stream: self.tcp_stream.clone(),
+            replay_requests: self.replay_requests.clone(),
+            event_sender: self.event_sender.clone(),
+        };
+
+        thread::spawn(move || {
+            let mut frame_reader = FrameReader::new(tcp_clone);
+            loop {
+                match frame_reader.read_frame() {
+                    Ok(Some(frame)) => {
+                        let msg = String::from_utf8_lossy(frame.as_slice()).to_string();
+                        messages.send(msg).expect("READER CANNOT SEND MESSAGE");
+                        continue;
+                    },
+                    Ok(None) => {
+                        eprintln!("IbRustApi Error: socket closed by the server, disconnecting");
+                    },
+                    Err(err) => {
+                        eprintln!("IbRustApi Error: {}, disconnecting", err);
                     }
                 }
-            });
-        }
+
+                match reconnector.run() {
+                    Some(tcp) => frame_reader = FrameReader::new(tcp),
+                    None => return
+                }
+            }
+        });
 
         Ok(())
     }
@@ -262,16 +317,31 @@ impl ClientSocket {
     fn parse_messages(&mut self, msg_queue: Receiver<String>) -> Result<(), Box<dyn Error>> {
         let event_sender = self.event_sender.clone();
         let server_version = self.server_version.clone();
+        let time_zones = self.time_zones.clone();
+        let subscriptions = self.subscriptions.clone();
+        let replay_requests = self.replay_requests.clone();
+        let (decoder_sender, decoder_receiver) = unbounded();
 
         thread::spawn(move || {
             loop {
-                let msg = msg_queue.recv();
-                if let Ok(msg) = msg {
-                    let fields = Self::read_fields((&msg).as_ref());
-                    let mut decoder = Decoder::new(fields.as_slice());
-                    decoder.process_incoming_message(server_version, &event_sender).unwrap_or_else(|err| {
-                        eprintln!("ib_rust_api error process_incoming_message: {}", err.to_string());
-                    });
+                let msg = match msg_queue.recv() {
+                    Ok(msg) => msg,
+                    Err(_) => {
+                        // the reader gave up reconnecting, no more messages will come
+                        event_sender.send(IncomingMessagesEnum::Disconnected).unwrap_or_default();
+                        break;
+                    }
+                };
+
+                let fields = Self::read_fields(msg.as_str());
+                let mut decoder = Decoder::new(fields.as_slice()).with_time_zones(&time_zones);
+                decoder.process_incoming_message(server_version.load(Ordering::SeqCst), &decoder_sender).unwrap_or_else(|err| {
+                    eprintln!("ib_rust_api error process_incoming_message: {}", err);
+                });
+
+                for event in decoder_receiver.try_iter() {
+                    end_failed_stream(&event, &subscriptions, &replay_requests);
+                    event_sender.send(event).unwrap_or_default();
                 }
             }
         });
@@ -279,7 +349,7 @@ impl ClientSocket {
         Ok(())
     }
 
-    fn read_fields(buf: &str) -> Vec<String> {
+    pub(crate) fn read_fields(buf: &str) -> Vec<String> {
         //msg payload is made of fields terminated/separated by NULL chars
         let a = '\u{0}';
         let mut fields: Vec<&str> = buf.split(a).collect::<Vec<&str>>();
@@ -293,3 +363,238 @@ impl ClientSocket {
             .collect::<Vec<String>>()
     }
 }
+
+impl Reconnector {
+    // Blocks until a new session is up, the subscriptions are replayed and the reader half of the
+    // new socket can be handed back. Returns None once the owning ClientSocket has been dropped.
+    fn run(&self) -> Option<TcpStream> {
+        if let Some(tcp) = self.tcp_stream.lock().unwrap().take() {
+            tcp.shutdown(Shutdown::Both).unwrap_or_default();
+        }
+
+        self.event_sender.send(IncomingMessagesEnum::Disconnected).unwrap_or_default();
+
+        let mut delay = Duration::from_millis(helper_constants::RECONNECT_INITIAL_DELAY_MS);
+        let max_delay = Duration::from_millis(helper_constants::RECONNECT_MAX_DELAY_MS);
+
+        loop {
+            thread::sleep(delay);
+
+            if Arc::strong_count(&self.tcp_stream) == 1 {
+                return None;
+            }
+
+            match self.restore_session() {
+                Ok(tcp) => {
+                    self.event_sender.send(IncomingMessagesEnum::Reconnected).unwrap_or_default();
+                    return Some(tcp);
+                },
+                Err(err) => {
+                    eprintln!("ib_rust_api reconnect failed, retrying in {:?}: {}", delay, err);
+                    delay = next_delay(delay, max_delay);
+                }
+            }
+        }
+    }
+
+    fn restore_session(&self) -> Result<TcpStream, Box<dyn Error>> {
+        let (mut tcp, server_version, _server_time) = ClientSocket::open_session(self.address.as_str(), self.client_id)?;
+
+        let previous_server_version = self.server_version.swap(server_version, Ordering::SeqCst);
+        if server_version != previous_server_version {
+            self.event_sender.send(IncomingMessagesEnum::ServerVersionChanged(previous_server_version, server_version)).unwrap_or_default();
+        }
+
+        // hold the replay lock while installing the stream so a request tracked in between
+        // is either replayed here or sent by the client, never both
+        let replay_requests = self.replay_requests.lock().unwrap();
+        for buffer in replay_requests.values() {
+            ClientSocket::write_buffer(&mut tcp, buffer)?;
+        }
+
+        let tcp_clone = tcp.try_clone()?;
+        *self.tcp_stream.lock().unwrap() = Some(tcp);
+
+        Ok(tcp_clone)
+    }
+}
+
+// An error for a req_id ends its stream, unless it is one of the warnings sent along with
+// live data, e.g. delayed market data being displayed.
+pub(crate) fn end_failed_stream(event: &IncomingMessagesEnum, subscriptions: &Subscriptions, replay_requests: &ReplayRequests) {
+    let (req_id, code) = match event {
+        IncomingMessagesEnum::Error(req_id, code, _) => (*req_id, *code),
+        _ => return,
+    };
+
+    if req_id < 0 || (2100..2200).contains(&code) || code == 10090 || code == 10167 {
+        return;
+    }
+
+    subscriptions.lock().unwrap().retain(|(_, id), _| *id != req_id);
+    replay_requests.lock().unwrap().retain(|(_, id), _| *id != req_id);
+}
+
+fn next_delay(delay: Duration, max_delay: Duration) -> Duration {
+    std::cmp::min(delay * 2, max_delay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn connected_socket() -> (ClientSocket, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let tcp = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+
+        let (sender, _receiver) = unbounded();
+        let client_socket = ClientSocket::new("127.0.0.1".to_string(), 0, 0, sender);
+        *client_socket.tcp_stream.lock().unwrap() = Some(tcp);
+
+        (client_socket, server)
+    }
+
+    fn request(request: OutgoingMessages, req_id: i32) -> (ApiParameters, u32) {
+        let mut params = ApiParameters::new();
+        let length_pos = params.prepare_buffer(true);
+        params.add_int(request as i32);
+        params.add_int(req_id);
+        (params, length_pos)
+    }
+
+    // Accepts sessions and acknowledges each handshake with server_version.
+    fn fake_tws(server_version: i32, sessions: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        thread::spawn(move || {
+            let mut streams = vec![];
+            for _ in 0..sessions {
+                let (mut tcp, _) = listener.accept().unwrap();
+
+                let mut prefix = [0_u8; 4];
+                tcp.read_exact(&mut prefix).unwrap();
+                ClientSocket::read_single_message(&mut tcp).unwrap();
+
+                let ack = format!("{}\u{0}20260101 00:00:00 UTC\u{0}", server_version).into_bytes();
+                tcp.write_all(&(ack.len() as i32).to_be_bytes()).unwrap();
+                tcp.write_all(ack.as_slice()).unwrap();
+                streams.push(tcp);
+            }
+            thread::sleep(Duration::from_secs(1));
+        });
+
+        address
+    }
+
+    #[test]
+    fn reconnecting_to_a_new_server_version_updates_it() {
+        let (sender, receiver) = unbounded();
+        let mut client_socket = ClientSocket::new("127.0.0.1".to_string(), 0, 0, sender.clone());
+        client_socket.set_server_version(100);
+
+        let reconnector = Reconnector {
+            address: fake_tws(151, 2),
+            client_id: 0,
+            server_version: client_socket.server_version.clone(),
+            tcp_stream: client_socket.tcp_stream.clone(),
+            replay_requests: client_socket.replay_requests.clone(),
+            event_sender: sender,
+        };
+
+        reconnector.restore_session().unwrap();
+        assert_eq!(client_socket.server_version(), 151);
+        assert!(matches!(receiver.try_recv(), Ok(IncomingMessagesEnum::ServerVersionChanged(100, 151))));
+
+        reconnector.restore_session().unwrap();
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[test]
+    fn parser_stops_when_the_reader_is_gone() {
+        let (sender, receiver) = unbounded();
+        let mut client_socket = ClientSocket::new("127.0.0.1".to_string(), 0, 0, sender);
+        let (messages, msg_queue) = unbounded();
+
+        client_socket.parse_messages(msg_queue).unwrap();
+        drop(messages);
+
+        assert!(matches!(receiver.recv_timeout(Duration::from_secs(1)), Ok(IncomingMessagesEnum::Disconnected)));
+    }
+
+    #[test]
+    fn reconnect_delay_doubles_up_to_the_maximum() {
+        let max_delay = Duration::from_millis(helper_constants::RECONNECT_MAX_DELAY_MS);
+        let mut delay = Duration::from_millis(helper_constants::RECONNECT_INITIAL_DELAY_MS);
+
+        let mut delays = vec![];
+        for _ in 0..8 {
+            delay = next_delay(delay, max_delay);
+            delays.push(delay.as_secs());
+        }
+
+        assert_eq!(delays, vec![2, 4, 8, 16, 32, 60, 60, 60]);
+    }
+
+    #[test]
+    fn requests_sharing_a_req_id_are_replayed_separately() {
+        let (mut client_socket, _server) = connected_socket();
+
+        let (mut params, length_pos) = request(OutgoingMessages::RequestMarketData, 7);
+        client_socket.close_and_send_replayable(OutgoingMessages::RequestMarketData, 7, &mut params, length_pos).unwrap();
+        let (mut params, length_pos) = request(OutgoingMessages::RequestHistoricalData, 7);
+        client_socket.close_and_send_replayable(OutgoingMessages::RequestHistoricalData, 7, &mut params, length_pos).unwrap();
+        assert_eq!(client_socket.replay_requests.lock().unwrap().len(), 2);
+
+        client_socket.forget_replayable(OutgoingMessages::RequestMarketData, 7);
+        let replay_requests = client_socket.replay_requests.lock().unwrap();
+        assert_eq!(replay_requests.keys().collect::<Vec<_>>(), vec![&(OutgoingMessages::RequestHistoricalData as i32, 7)]);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn buffered_requests_are_not_kept_for_replay() {
+        let (sender, _receiver) = unbounded();
+        let mut client_socket = ClientSocket::new_buffered(0, 151, sender);
+
+        let (mut params, length_pos) = request(OutgoingMessages::RequestMarketData, 7);
+        client_socket.close_and_send_replayable(OutgoingMessages::RequestMarketData, 7, &mut params, length_pos).unwrap();
+
+        assert_eq!(client_socket.take_outbox().len(), 1);
+        assert!(client_socket.replay_requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn errors_end_the_streams_of_their_req_id() {
+        let (mut client_socket, _server) = connected_socket();
+
+        for (message, subscription) in [(OutgoingMessages::RequestMarketData, Subscription::MarketData), (OutgoingMessages::RequestRealTimeBars, Subscription::RealTimeBars)] {
+            let (mut params, length_pos) = request(message, 7);
+            client_socket.close_and_send_replayable(message, 7, &mut params, length_pos).unwrap();
+            client_socket.track_subscription(message, 7, subscription);
+        }
+        client_socket.forget_replayable(OutgoingMessages::RequestRealTimeBars, 7);
+        assert_eq!(client_socket.subscriptions.lock().unwrap().values().collect::<Vec<_>>(), vec![&Subscription::MarketData]);
+
+        let warning = IncomingMessagesEnum::Error(7, 10167, "Displaying delayed market data".to_string());
+        end_failed_stream(&warning, &client_socket.subscriptions, &client_socket.replay_requests);
+        assert_eq!(client_socket.subscriptions.lock().unwrap().len(), 1);
+
+        let error = IncomingMessagesEnum::Error(7, 200, "No security definition has been found for the request".to_string());
+        end_failed_stream(&error, &client_socket.subscriptions, &client_socket.replay_requests);
+        assert!(client_socket.subscriptions.lock().unwrap().is_empty());
+        assert!(client_socket.replay_requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn failed_sends_are_not_replayed() {
+        let (sender, _receiver) = unbounded();
+        let mut client_socket = ClientSocket::new("127.0.0.1".to_string(), 0, 0, sender);
+
+        let (mut params, length_pos) = request(OutgoingMessages::RequestMarketData, 7);
+        assert!(client_socket.close_and_send_replayable(OutgoingMessages::RequestMarketData, 7, &mut params, length_pos).is_err());
+        assert!(client_socket.replay_requests.lock().unwrap().is_empty());
+    }
+}
@@ -9,7 +9,14 @@ pub const BAG_SEC_TYPE: &str = "BAG";
 //pub const MAX_VERSION: i32 = min_server_version::PRICE_MGMT_ALGO;
 pub const MAX_MSG_SIZE: i32 = 0x00FFFFFF;
 
-//pub const NO_VALID_ID: i32 = -1;
+pub const RECONNECT_INITIAL_DELAY_MS: u64 = 1_000;
+pub const RECONNECT_MAX_DELAY_MS: u64 = 60_000;
+
+// subscriptions that are not tied to a request id are tracked for replay under these keys
+pub const POSITIONS_REPLAY_ID: i32 = -2;
+pub const ACCOUNT_UPDATES_REPLAY_ID: i32 = -3;
+
+pub const NO_VALID_ID: i32 = -1;
 //pub const MAX_MSG_LEN: i64 = 0xFFFFFF; //16Mb - 1byte
 
 pub const UNSET_INTEGER: i32 = std::i32::MAX;
@@ -1,17 +1,17 @@
 pub const NOT_VALID: i32 = -1;
-//pub const TICK_PRICE: i32 = 1;
-//pub const TICK_SIZE: i32 = 2;
+pub const TICK_PRICE: i32 = 1;
+pub const TICK_SIZE: i32 = 2;
 pub const ORDER_STATUS: i32 = 3;
 pub const ERROR: i32 = 4;
 pub const OPEN_ORDER: i32 = 5;
-//pub const ACCOUNT_VALUE: i32 = 6;
-//pub const PORTFOLIO_VALUE: i32 = 7;
-//pub const ACCOUNT_UPDATE_TIME: i32 = 8;
+pub const ACCOUNT_VALUE: i32 = 6;
+pub const PORTFOLIO_VALUE: i32 = 7;
+pub const ACCOUNT_UPDATE_TIME: i32 = 8;
 pub const NEXT_VALID_ID: i32 = 9;
 pub const CONTRACT_DATA: i32 = 10;
-//pub const EXECUTION_DATA: i32 = 11;
-//pub const MARKET_DEPTH: i32 = 12;
-//pub const MARKET_DEPTH_L2: i32 = 13;
+pub const EXECUTION_DATA: i32 = 11;
+pub const MARKET_DEPTH: i32 = 12;
+pub const MARKET_DEPTH_L2: i32 = 13;
 //pub const NEWS_BULLETINS: i32 = 14;
 pub const MANAGED_ACCOUNTS: i32 = 15;
 //pub const RECEIVE_FA: i32 = 16;
@@ -19,7 +19,7 @@ pub const HISTORICAL_DATA: i32 = 17;
 //pub const BOND_CONTRACT_DATA: i32 = 18;
 //pub const SCANNER_PARAMETERS: i32 = 19;
 pub const SCANNER_DATA: i32 = 20;
-//pub const TICK_OPTION_COMPUTATION: i32 = 21;
+pub const TICK_OPTION_COMPUTATION: i32 = 21;
 pub const TICK_GENERIC: i32 = 45;
 //pub const TICKSTRING: i32 = 46;
 //pub const TICK_EFP: i32 = 47;//TICK EFP 47
@@ -28,14 +28,14 @@ pub const REAL_TIME_BARS: i32 = 50;
 //pub const FUNDAMENTAL_DATA: i32 = 51;
 pub const CONTRACT_DATA_END: i32 = 52;
 pub const OPEN_ORDER_END: i32 = 53;
-//pub const ACCOUNT_DOWNLOAD_END: i32 = 54;
-//pub const EXECUTION_DATA_END: i32 = 55;
+pub const ACCOUNT_DOWNLOAD_END: i32 = 54;
+pub const EXECUTION_DATA_END: i32 = 55;
 //pub const DELTA_NEUTRAL_VALIDATION: i32 = 56;
 //pub const TICK_SNAPSHOT_END: i32 = 57;
 //pub const MARKET_DATA_TYPE: i32 = 58;
-//pub const COMMISSIONS_REPORT: i32 = 59;
-//pub const POSITION: i32 = 61;
-//pub const POSITION_END: i32 = 62;
+pub const COMMISSIONS_REPORT: i32 = 59;
+pub const POSITION: i32 = 61;
+pub const POSITION_END: i32 = 62;
 pub const ACCOUNT_SUMMARY: i32 = 63;
 pub const ACCOUNT_SUMMARY_END: i32 = 64;
 //pub const VERIFY_MESSAGE_API: i32 = 65;
@@ -48,8 +48,8 @@ pub const ACCOUNT_SUMMARY_END: i32 = 64;
 //pub const POSITION_MULTI_END: i32 = 72;
 //pub const ACCOUNT_UPDATE_MULTI: i32 = 73;
 //pub const ACCOUNT_UPDATE_MULTI_END: i32 = 74;
-//pub const SECURITY_DEFINITION_OPTION_PARAMETER: i32 = 75;
-//pub const SECURITY_DEFINITION_OPTION_PARAMETER_END: i32 = 76;
+pub const SECURITY_DEFINITION_OPTION_PARAMETER: i32 = 75;
+pub const SECURITY_DEFINITION_OPTION_PARAMETER_END: i32 = 76;
 //pub const SOFT_DOLLAR_TIER: i32 = 77;
 //pub const FAMILY_CODES: i32 = 78;
 //pub const SYMBOL_SAMPLES: i32 = 79;
@@ -61,18 +61,18 @@ pub const ACCOUNT_SUMMARY_END: i32 = 64;
 //pub const NEWS_PROVIDERS: i32 = 85;
 //pub const HISTORICAL_NEWS: i32 = 86;
 //pub const HISTORICAL_NEWS_END: i32 = 87;
-//pub const HEAD_TIMESTAMP: i32 = 88;
-//pub const HISTOGRAM_DATA: i32 = 89;
-//pub const HISTORICAL_DATA_UPDATE: i32 = 90;
+pub const HEAD_TIMESTAMP: i32 = 88;
+pub const HISTOGRAM_DATA: i32 = 89;
+pub const HISTORICAL_DATA_UPDATE: i32 = 90;
 //pub const REROUTE_MKT_DATA_REQ: i32 = 91;
 //pub const REROUTE_MKT_DEPTH_REQ: i32 = 92;
 //pub const MARKET_RULE: i32 = 93;
 pub const PN_L: i32 = 94;
-//pub const PN_LSINGLE: i32 = 95;
-//pub const HISTORICAL_TICK: i32 = 96;
-//pub const HISTORICAL_TICK_BID_ASK: i32 = 97;
-//pub const HISTORICAL_TICK_LAST: i32 = 98;
+pub const PN_LSINGLE: i32 = 95;
+pub const HISTORICAL_TICK: i32 = 96;
+pub const HISTORICAL_TICK_BID_ASK: i32 = 97;
+pub const HISTORICAL_TICK_LAST: i32 = 98;
 pub const TICK_BY_TICK: i32 = 99;
-//pub const ORDER_BOUND: i32 = 100;
-//pub const COMPLETED_ORDER: i32 = 101;
-//pub const COMPLETED_ORDERS_END: i32 = 102;
+pub const ORDER_BOUND: i32 = 100;
+pub const COMPLETED_ORDER: i32 = 101;
+pub const COMPLETED_ORDERS_END: i32 = 102;
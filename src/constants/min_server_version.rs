@@ -1,7 +1,8 @@
 //pub const MIN_VERSION: i32 = 38;
 
 ////shouldn't these all be deprecated?
-//pub const HISTORICAL_DATA: i32 = 24;
+pub const EXERCISE_OPTIONS: i32 = 21;
+pub const HISTORICAL_DATA: i32 = 24;
 //pub const CURRENT_TIME: i32 = 33;
 pub const REAL_TIME_BARS: i32 = 34;
 pub const SCALE_ORDERS: i32 = 35;
@@ -16,15 +17,15 @@ pub const DELTA_NEUTRAL: i32 = 40;
 pub const CONTRACT_DATA_CHAIN: i32 = 40;
 pub const SCALE_ORDERS2: i32 = 40;
 pub const ALGO_ORDERS: i32 = 41;
-//pub const EXECUTION_DATA_CHAIN: i32 = 42;
+pub const EXECUTION_DATA_CHAIN: i32 = 42;
 pub const NOT_HELD: i32 = 44;
 pub const SEC_ID_TYPE: i32 = 45;
 pub const PLACE_ORDER_CONID: i32 = 46;
 //pub const REQ_MKT_DATA_CONID: i32 = 47;
-//pub const REQ_CALC_IMPLIED_VOLAT: i32 = 49;
-//pub const REQ_CALC_OPTION_PRICE: i32 = 50;
-//pub const CANCEL_CALC_IMPLIED_VOLAT: i32 = 50;
-//pub const CANCEL_CALC_OPTION_PRICE: i32 = 50;
+pub const REQ_CALC_IMPLIED_VOLAT: i32 = 49;
+pub const REQ_CALC_OPTION_PRICE: i32 = 50;
+pub const CANCEL_CALC_IMPLIED_VOLAT: i32 = 50;
+pub const CANCEL_CALC_OPTION_PRICE: i32 = 50;
 pub const SSHORTX_OLD: i32 = 51;
 pub const SSHORTX: i32 = 52;
 pub const REQ_GLOBAL_CANCEL: i32 = 53;
@@ -38,6 +39,7 @@ pub const ORDER_COMBO_LEGS_PRICE: i32 = 61;
 pub const TRAILING_PERCENT: i32 = 62;
 pub const DELTA_NEUTRAL_OPEN_CLOSE: i32 = 66;
 pub const ACCT_SUMMARY: i32 = 67;
+pub const POSITIONS: i32 = 67;
 pub const TRADING_CLASS: i32 = 68;
 pub const SCALE_TABLE: i32 = 69;
 pub const LINKING: i32 = 70;
@@ -50,12 +52,12 @@ pub const RANDOMIZE_SIZE_AND_PRICE: i32 = 76;
 pub const FRACTIONAL_POSITIONS: i32 = 101;
 pub const PEGGED_TO_BENCHMARK: i32 = 102;
 pub const MODELS_SUPPORT: i32 = 103;
-//pub const SEC_DEF_OPT_PARAMS_REQ: i32 = 104;
+pub const SEC_DEF_OPT_PARAMS_REQ: i32 = 104;
 pub const EXT_OPERATOR: i32 = 105;
 pub const SOFT_DOLLAR_TIER: i32 = 106;
 //pub const REQ_FAMILY_CODES: i32 = 107;
 //pub const REQ_MATCHING_SYMBOLS: i32 = 108;
-//pub const PAST_LIMIT: i32 = 109;
+pub const PAST_LIMIT: i32 = 109;
 pub const MD_SIZE_MULTIPLIER: i32 = 110;
 pub const CASH_QTY: i32 = 111;
 //pub const REQ_MKT_DEPTH_EXCHANGES: i32 = 112;
@@ -64,24 +66,24 @@ pub const SMART_COMPONENTS: i32 = 114;
 //pub const REQ_NEWS_PROVIDERS: i32 = 115;
 //pub const REQ_NEWS_ARTICLE: i32 = 116;
 //pub const REQ_HISTORICAL_NEWS: i32 = 117;
-//pub const REQ_HEAD_TIMESTAMP: i32 = 118;
-//pub const REQ_HISTOGRAM_DATA: i32 = 119;
+pub const REQ_HEAD_TIMESTAMP: i32 = 118;
+pub const REQ_HISTOGRAM_DATA: i32 = 119;
 //pub const SERVICE_DATA_TYPE: i32 = 120;
 pub const AGG_GROUP: i32 = 121;
 pub const UNDERLYING_INFO: i32 = 122;
-//pub const CANCEL_HEADTIMESTAMP: i32 = 123;
+pub const CANCEL_HEADTIMESTAMP: i32 = 123;
 pub const SYNT_REALTIME_BARS: i32 = 124;
 //pub const CFD_REROUTE: i32 = 125;
 pub const MARKET_RULES: i32 = 126;
 pub const PNL: i32 = 127;
 //pub const NEWS_QUERY_ORIGINS: i32 = 128;
 pub const UNREALIZED_PNL: i32 = 129;
-//pub const HISTORICAL_TICKS: i32 = 130;
+pub const HISTORICAL_TICKS: i32 = 130;
 pub const MARKET_CAP_PRICE: i32 = 131;
-//pub const PRE_OPEN_BID_ASK: i32 = 132;
+pub const PRE_OPEN_BID_ASK: i32 = 132;
 pub const REAL_EXPIRATION_DATE: i32 = 134;
 pub const REALIZED_PNL: i32 = 135;
-//pub const LAST_LIQUIDITY: i32 = 136;
+pub const LAST_LIQUIDITY: i32 = 136;
 pub const TICK_BY_TICK: i32 = 137;
 pub const DECISION_MAKER: i32 = 138;
 pub const MIFID_EXECUTION: i32 = 139;
@@ -91,9 +93,11 @@ pub const WHAT_IF_EXT_FIELDS: i32 = 142;
 pub const SCANNER_GENERIC_OPTS: i32 = 143;
 //pub const API_BIND_ORDER: i32 = 144;
 pub const ORDER_CONTAINER: i32 = 145;
-//pub const SMART_DEPTH: i32 = 146;
+pub const SMART_DEPTH: i32 = 146;
 //pub const REMOVE_NULL_ALL_CASTING: i32 = 147;
 pub const D_PEG_ORDERS: i32 = 148;
-//pub const MKT_DEPTH_PRIM_EXCHANGE: i32 = 149;
-//pub const COMPLETED_ORDERS: i32 = 150;
-pub const PRICE_MGMT_ALGO: i32 = 151;
\ No newline at end of file
+pub const MKT_DEPTH_PRIM_EXCHANGE: i32 = 149;
+pub const COMPLETED_ORDERS: i32 = 150;
+pub const PRICE_MGMT_ALGO: i32 = 151;
+pub const PRICE_BASED_VOLATILITY: i32 = 156;
+pub const PENDING_PRICE_REVISION: i32 = 178;
\ No newline at end of file
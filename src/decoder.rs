@@ -16,11 +16,39 @@ use crate::models::bar::Bar;
 use std::str::FromStr;
 use std::slice::Iter;
 use std::error::Error;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use crate::constants::helper_constants::{UNSET_INTEGER, UNSET_DOUBLE};
 use crate::models::tick_last::TickLast;
+use crate::models::tick_attrib::TickAttrib;
+use crate::enums::tick_type::TickType;
+use crate::models::tick_attrib_last::TickAttribLast;
+use crate::models::tick_attrib_bid_ask::TickAttribBidAsk;
+use crate::models::tick_bid_ask::TickBidAsk;
+use crate::models::tick_mid_point::TickMidPoint;
+use crate::models::market_depth::MarketDepth;
+use crate::enums::market_depth_operation::MarketDepthOperation;
+use crate::enums::market_depth_side::MarketDepthSide;
+use crate::models::execution::Execution;
+use crate::models::commission_report::CommissionReport;
+use crate::models::position::Position;
+use crate::models::portfolio_item::PortfolioItem;
+use crate::models::account_value::AccountValue;
+use crate::models::option_chain::OptionChain;
+use crate::models::option_computation::OptionComputation;
+use crate::models::historical_tick::HistoricalTick;
+use crate::models::histogram_entry::HistogramEntry;
+use crate::models::pnl::PnL;
+use crate::models::pnl_single::PnLSingle;
+use crate::models::order_bound::OrderBound;
+use crate::enums::volatility_basis::VolatilityBasis;
+
+// Time zones registered per req_id, used to place the local bar times of those requests.
+pub type TimeZones = Arc<Mutex<HashMap<i32, String>>>;
 
 pub struct Decoder {
-    fields: Vec<String>
+    fields: Vec<String>,
+    time_zones: Option<TimeZones>
 }
 
 pub fn decode_i32(iter: &mut Iter<String>) -> Result<i32, Box<dyn Error>> {
@@ -54,6 +82,12 @@ pub fn decode_f64_show_unset(iter: &mut Iter<String>) -> Result<f64, Box<dyn Err
     Ok(if rtn == 0.0 { UNSET_DOUBLE } else { rtn })
 }
 
+// IB sends -1 (prices, volatility) or -2 (greeks) for values it could not compute.
+fn decode_option_value(iter: &mut Iter<String>, not_computed: f64) -> Result<Option<f64>, Box<dyn Error>> {
+    let value = decode_f64(iter)?;
+    Ok(if value == not_computed || value == UNSET_DOUBLE { None } else { Some(value) })
+}
+
 pub fn decode_string(iter: &mut Iter<String>) -> Result<String, Box<dyn Error>> {
     let next = iter.next();
     let val = next.unwrap().parse().unwrap_or("".to_string());
@@ -70,10 +104,23 @@ pub fn decode_bool(iter: &mut Iter<String>) -> Result<bool, Box<dyn Error>> {
 impl Decoder {
     pub fn new(fields: &[String]) -> Decoder {
         Decoder {
-            fields: fields.to_vec()
+            fields: fields.to_vec(),
+            time_zones: None
         }
     }
 
+    pub fn with_time_zones(mut self, time_zones: &TimeZones) -> Self {
+        self.time_zones = Some(time_zones.clone());
+        self
+    }
+
+    fn set_bar_time(&self, req_id: i32, bar: &mut Bar) {
+        let time_zones = self.time_zones.as_ref().map(|time_zones| time_zones.lock().unwrap());
+        let time_zone_id = time_zones.as_ref().and_then(|time_zones| time_zones.get(&req_id)).map(String::as_str);
+
+        bar.set_time(Bar::parse_time(bar.time_str.as_str(), time_zone_id));
+    }
+
     pub fn process_connect_ack(mut msg: Cursor<Vec<u8>>, server_version: &mut i32, server_time: &mut String, is_connected: &mut bool) {
         *server_version = Self::read_int(&mut msg);
 
@@ -146,6 +193,23 @@ impl Decoder {
             incoming_message_constants::OPEN_ORDER_END => {
                 sender_messages_enum.send(IncomingMessagesEnum::OpenOrderEnd)?;
             },
+            incoming_message_constants::ORDER_BOUND => {
+                let mut fields_itr = self.fields.iter();
+                //throw away message_id
+                fields_itr.next();
+                sender_messages_enum.send(IncomingMessagesEnum::OrderBound(OrderBound {
+                    perm_id: decode_i64(&mut fields_itr)?,
+                    api_client_id: decode_i32(&mut fields_itr)?,
+                    api_order_id: decode_i32(&mut fields_itr)?,
+                }))?;
+            },
+            incoming_message_constants::COMPLETED_ORDER => {
+                let order_data = self.completed_order_event(server_version)?;
+                sender_messages_enum.send(IncomingMessagesEnum::CompletedOrder(order_data))?;
+            },
+            incoming_message_constants::COMPLETED_ORDERS_END => {
+                sender_messages_enum.send(IncomingMessagesEnum::CompletedOrdersEnd)?;
+            },
             incoming_message_constants::CONTRACT_DATA => {
                 let (req_id, data) = self.contract_data_event(server_version)?;
                 sender_messages_enum.send(IncomingMessagesEnum::ContractData(req_id, data))?;
@@ -169,12 +233,108 @@ impl Decoder {
             incoming_message_constants::TICK_BY_TICK => {
                 self.tick_by_tick_event(&sender_messages_enum)?;
             },
+            incoming_message_constants::TICK_PRICE => {
+                self.tick_price_event(server_version, sender_messages_enum)?;
+            },
+            incoming_message_constants::TICK_SIZE => {
+                self.tick_size_event(sender_messages_enum)?;
+            },
+            incoming_message_constants::MARKET_DEPTH => {
+                self.market_depth_event(sender_messages_enum)?;
+            },
+            incoming_message_constants::MARKET_DEPTH_L2 => {
+                self.market_depth_l2_event(server_version, sender_messages_enum)?;
+            },
+            incoming_message_constants::EXECUTION_DATA => {
+                self.execution_data_event(server_version, sender_messages_enum)?;
+            },
+            incoming_message_constants::EXECUTION_DATA_END => {
+                let mut fields_itr = self.fields.iter();
+                //throw away message_id
+                fields_itr.next();
+                //throw away version
+                fields_itr.next();
+                let req_id = decode_i32(&mut fields_itr)?;
+                sender_messages_enum.send(IncomingMessagesEnum::ExecutionDataEnd(req_id))?;
+            },
+            incoming_message_constants::COMMISSIONS_REPORT => {
+                self.commission_report_event(sender_messages_enum)?;
+            },
+            incoming_message_constants::ACCOUNT_VALUE => {
+                self.account_value_event(sender_messages_enum)?;
+            },
+            incoming_message_constants::PORTFOLIO_VALUE => {
+                self.portfolio_value_event(server_version, sender_messages_enum)?;
+            },
+            incoming_message_constants::ACCOUNT_UPDATE_TIME => {
+                let mut fields_itr = self.fields.iter();
+                //throw away message_id
+                fields_itr.next();
+                //throw away version
+                fields_itr.next();
+                let time_stamp = decode_string(&mut fields_itr)?;
+                sender_messages_enum.send(IncomingMessagesEnum::AccountUpdateTime(time_stamp))?;
+            },
+            incoming_message_constants::ACCOUNT_DOWNLOAD_END => {
+                let mut fields_itr = self.fields.iter();
+                //throw away message_id
+                fields_itr.next();
+                //throw away version
+                fields_itr.next();
+                let account_name = decode_string(&mut fields_itr)?;
+                sender_messages_enum.send(IncomingMessagesEnum::AccountDownloadEnd(account_name))?;
+            },
+            incoming_message_constants::POSITION => {
+                self.position_event(server_version, sender_messages_enum)?;
+            },
+            incoming_message_constants::POSITION_END => {
+                sender_messages_enum.send(IncomingMessagesEnum::PositionEnd)?;
+            },
+            incoming_message_constants::SECURITY_DEFINITION_OPTION_PARAMETER => {
+                self.security_definition_option_parameter_event(sender_messages_enum)?;
+            },
+            incoming_message_constants::SECURITY_DEFINITION_OPTION_PARAMETER_END => {
+                let mut fields_itr = self.fields.iter();
+                //throw away message_id
+                fields_itr.next();
+                let req_id = decode_i32(&mut fields_itr)?;
+                sender_messages_enum.send(IncomingMessagesEnum::SecurityDefinitionOptionParameterEnd(req_id))?;
+            },
+            incoming_message_constants::TICK_OPTION_COMPUTATION => {
+                self.tick_option_computation_event(server_version, sender_messages_enum)?;
+            },
+            incoming_message_constants::HISTORICAL_DATA_UPDATE => {
+                self.historical_data_update_event(sender_messages_enum)?;
+            },
+            incoming_message_constants::HISTORICAL_TICK => {
+                self.historical_tick_event(sender_messages_enum)?;
+            },
+            incoming_message_constants::HISTORICAL_TICK_BID_ASK => {
+                self.historical_tick_bid_ask_event(sender_messages_enum)?;
+            },
+            incoming_message_constants::HISTORICAL_TICK_LAST => {
+                self.historical_tick_last_event(sender_messages_enum)?;
+            },
+            incoming_message_constants::HEAD_TIMESTAMP => {
+                let mut fields_itr = self.fields.iter();
+                //throw away message_id
+                fields_itr.next();
+                let req_id = decode_i32(&mut fields_itr)?;
+                let head_timestamp = decode_string(&mut fields_itr)?;
+                sender_messages_enum.send(IncomingMessagesEnum::HeadTimestamp(req_id, head_timestamp))?;
+            },
+            incoming_message_constants::HISTOGRAM_DATA => {
+                self.histogram_data_event(sender_messages_enum)?;
+            },
             incoming_message_constants::TICK_GENERIC => {
                 self.generic_tick(&sender_messages_enum)?;
             },
             incoming_message_constants::PN_L => {
                 self.profit_and_losses(server_version, &sender_messages_enum)?;
             },
+            incoming_message_constants::PN_LSINGLE => {
+                self.profit_and_losses_single(server_version, sender_messages_enum)?;
+            },
             _ => {}
         }
 
@@ -352,6 +512,9 @@ impl Decoder {
     fn open_order_event(&mut self, server_version: i32) -> Result<OrderDataItem, Box<dyn Error>> {
         let mut fields_itr = self.fields.iter();
 
+        //throw away message_id
+        fields_itr.next();
+
         let msg_version = if server_version < min_server_version::ORDER_CONTAINER {
             decode_i32(&mut fields_itr)?
         }
@@ -440,6 +603,86 @@ impl Decoder {
         Ok(order_data_item)
     }
 
+    // Completed orders carry no order id nor message version, and leave out the fields that only
+    // matter while an order is working.
+    fn completed_order_event(&mut self, server_version: i32) -> Result<OrderDataItem, Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let mut order = Order::new();
+        let mut contract = Contract::new();
+        let mut order_state = OrderState::new();
+        let mut order_decoder = OrderDecoder::new(&mut contract, &mut order, &mut order_state, i32::MAX, server_version, fields_itr);
+
+        order_decoder.read_contract_fields()?;
+
+        order_decoder.read_action()?;
+        order_decoder.read_total_quantity()?;
+        order_decoder.read_order_type()?;
+        order_decoder.read_limit_type()?;
+        order_decoder.read_aux_price()?;
+        order_decoder.read_tif()?;
+        order_decoder.read_oca_group()?;
+        order_decoder.read_account()?;
+        order_decoder.read_open_close()?;
+        order_decoder.read_origin()?;
+        order_decoder.read_order_ref()?;
+        order_decoder.read_perm_id()?;
+        order_decoder.read_outside_rth()?;
+        order_decoder.read_hidden()?;
+        order_decoder.read_discretionary_amount()?;
+        order_decoder.read_good_after_time()?;
+        order_decoder.read_faparams()?;
+        order_decoder.read_model_code()?;
+        order_decoder.read_good_till_date()?;
+        order_decoder.read_rule80a()?;
+        order_decoder.read_percent_offset()?;
+        order_decoder.read_settling_firm()?;
+        order_decoder.read_short_sale_params()?;
+        order_decoder.read_box_order_params()?;
+        order_decoder.read_peg_to_stk_or_vol_order_params()?;
+        order_decoder.read_display_size()?;
+        order_decoder.read_sweep_to_fill()?;
+        order_decoder.read_all_or_none()?;
+        order_decoder.read_min_qty()?;
+        order_decoder.read_oca_type()?;
+        order_decoder.read_trigger_method()?;
+        order_decoder.read_vol_order_params(false)?;
+        order_decoder.read_trail_params()?;
+        order_decoder.read_combo_legs()?;
+        order_decoder.read_smart_combo_routing_params()?;
+        order_decoder.read_scale_order_params()?;
+        order_decoder.read_hedge_params()?;
+        order_decoder.read_clearing_params()?;
+        order_decoder.read_not_held()?;
+        order_decoder.read_delta_neutral()?;
+        order_decoder.read_algo_params()?;
+        order_decoder.read_solicited()?;
+        order_decoder.read_order_status()?;
+        order_decoder.read_vol_randomize_flags()?;
+        order_decoder.read_peg_to_bench_params()?;
+        order_decoder.read_conditions()?;
+        order_decoder.read_stop_price_and_lmt_price_offset()?;
+        order_decoder.read_cash_qty()?;
+        order_decoder.read_dont_use_auto_price_for_hedge()?;
+        order_decoder.read_is_oms_container()?;
+        order_decoder.read_auto_cancel_date()?;
+        order_decoder.read_filled_quantity()?;
+        order_decoder.read_ref_futures_con_id()?;
+        order_decoder.read_auto_cancel_parent()?;
+        order_decoder.read_shareholder()?;
+        order_decoder.read_imbalance_only()?;
+        order_decoder.read_route_marketable_to_bbo()?;
+        order_decoder.read_parent_perm_id()?;
+        order_decoder.read_completed_time()?;
+        order_decoder.read_completed_status()?;
+
+        let order_data_item = OrderDataItem::new(order.order_id, order, contract, order_state);
+        Ok(order_data_item)
+    }
+
     fn contract_data_event(&mut self, server_version: i32) -> Result<(i32, ContractDetails), Box<dyn Error>> {
         let mut contract = ContractDetails::new();
 
@@ -655,9 +898,10 @@ impl Decoder {
 
             let bar_count = decode_i32(&mut fields_itr)?; // ver 3 field
 
-            sender.send(IncomingMessagesEnum::HistoricalData(Bar {
-                time_str: date,
+            let mut bar = Bar {
                 time_int: 0,
+                has_time: false,
+                time_str: date,
                 open,
                 high,
                 low,
@@ -666,7 +910,10 @@ impl Decoder {
                 wap: average,
                 count: bar_count as f64,
                 color: None
-            }))?;
+            };
+            self.set_bar_time(req_id, &mut bar);
+
+            sender.send(IncomingMessagesEnum::HistoricalData(req_id, bar))?;
         }
 
         sender.send(IncomingMessagesEnum::HistoricalDataEnd(req_id, start_date, end_date))?;
@@ -674,6 +921,42 @@ impl Decoder {
         Ok(())
     }
 
+    fn historical_data_update_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let bar_count = decode_i32(&mut fields_itr)?;
+        let date = decode_string(&mut fields_itr)?;
+        let open = decode_f64(&mut fields_itr)?;
+        let close = decode_f64(&mut fields_itr)?;
+        let high = decode_f64(&mut fields_itr)?;
+        let low = decode_f64(&mut fields_itr)?;
+        let average = decode_f64(&mut fields_itr)?;
+        let volume = decode_i64(&mut fields_itr)?;
+
+        let mut bar = Bar {
+            time_int: 0,
+            has_time: false,
+            time_str: date,
+            open,
+            high,
+            low,
+            close,
+            volume: volume as f64,
+            wap: average,
+            count: bar_count as f64,
+            color: None
+        };
+        self.set_bar_time(req_id, &mut bar);
+
+        sender.send(IncomingMessagesEnum::HistoricalDataUpdate(req_id, bar))?;
+
+        Ok(())
+    }
+
     fn read_time_bars_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
         let mut fields_itr = self.fields.iter();
 
@@ -684,9 +967,11 @@ impl Decoder {
 
         let req_id = decode_i32(&mut fields_itr)?;
 
-        let bar = Bar {
-            time_str: decode_string(&mut fields_itr)?,
+        let time_str = decode_string(&mut fields_itr)?;
+        let mut bar = Bar {
             time_int: 0,
+            has_time: false,
+            time_str,
             open: decode_f64(&mut fields_itr)?,
             high: decode_f64(&mut fields_itr)?,
             low: decode_f64(&mut fields_itr)?,
@@ -696,12 +981,129 @@ impl Decoder {
             count: decode_i32(&mut fields_itr)? as f64,
             color: None
         };
+        self.set_bar_time(req_id, &mut bar);
 
         sender.send(IncomingMessagesEnum::RealTimeBars(req_id, bar))?;
 
         Ok(())
     }
 
+    fn histogram_data_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let count = decode_i32(&mut fields_itr)?;
+
+        let mut entries = vec![];
+        for _ in 0..count {
+            entries.push(HistogramEntry {
+                price: decode_f64(&mut fields_itr)?,
+                size: decode_i64(&mut fields_itr)?,
+            });
+        }
+
+        sender.send(IncomingMessagesEnum::HistogramData(req_id, entries))?;
+
+        Ok(())
+    }
+
+    fn historical_tick_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let tick_count = decode_i32(&mut fields_itr)?;
+
+        let mut ticks = vec![];
+        for _ in 0..tick_count {
+            let time = decode_i64(&mut fields_itr)?;
+            //throw away unused field
+            fields_itr.next();
+            let price = decode_f64(&mut fields_itr)?;
+            let size = decode_i64(&mut fields_itr)?;
+
+            ticks.push(HistoricalTick { time, price, size });
+        }
+
+        let done = decode_bool(&mut fields_itr)?;
+
+        sender.send(IncomingMessagesEnum::HistoricalTick(req_id, ticks, done))?;
+
+        Ok(())
+    }
+
+    fn historical_tick_bid_ask_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let tick_count = decode_i32(&mut fields_itr)?;
+
+        let mut ticks = vec![];
+        for _ in 0..tick_count {
+            let time = decode_i64(&mut fields_itr)?;
+            let mask = decode_i32(&mut fields_itr)?;
+
+            ticks.push(TickBidAsk {
+                time,
+                bid_price: decode_f64(&mut fields_itr)?,
+                ask_price: decode_f64(&mut fields_itr)?,
+                bid_size: decode_i32(&mut fields_itr)?,
+                ask_size: decode_i32(&mut fields_itr)?,
+                // unlike tick-by-tick data, the first bit is the ask one here
+                tick_attrib_bid_ask: TickAttribBidAsk {
+                    ask_past_high: mask & 1 != 0,
+                    bid_past_low: mask & 2 != 0,
+                }
+            });
+        }
+
+        let done = decode_bool(&mut fields_itr)?;
+
+        sender.send(IncomingMessagesEnum::HistoricalTickBidAsk(req_id, ticks, done))?;
+
+        Ok(())
+    }
+
+    fn historical_tick_last_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let tick_count = decode_i32(&mut fields_itr)?;
+
+        let mut ticks = vec![];
+        for _ in 0..tick_count {
+            let time = decode_i64(&mut fields_itr)?;
+            let mask = decode_i32(&mut fields_itr)?;
+
+            ticks.push(TickLast {
+                time,
+                price: decode_f64(&mut fields_itr)?,
+                size: decode_i32(&mut fields_itr)?,
+                mask,
+                tick_attrib_last: TickAttribLast::from(mask),
+                exchange: decode_string(&mut fields_itr)?,
+                special_conditions: decode_string(&mut fields_itr)?
+            });
+        }
+
+        let done = decode_bool(&mut fields_itr)?;
+
+        sender.send(IncomingMessagesEnum::HistoricalTickLast(req_id, ticks, done))?;
+
+        Ok(())
+    }
+
     fn tick_by_tick_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
         let mut fields_itr = self.fields.iter();
 
@@ -721,9 +1123,6 @@ impl Decoder {
                     let price = decode_f64(&mut fields_itr)?;
                     let size = decode_i32(&mut fields_itr)?;
                     let mask = decode_i32(&mut fields_itr)?;
-//                    let mut tick_attrib_last = TickAttribLast::default();
-//                    tick_attrib_last.past_limit = mask & 1 != 0;
-//                    tick_attrib_last.unreported = mask & 2 != 0;
                     let exchange = decode_string(&mut fields_itr)?;
                     let special_conditions = decode_string(&mut fields_itr)?;
 
@@ -732,6 +1131,7 @@ impl Decoder {
                         price,
                         size,
                         mask,
+                        tick_attrib_last: TickAttribLast::from(mask),
                         exchange,
                         special_conditions
                     };
@@ -741,41 +1141,93 @@ impl Decoder {
             3 =>
             // BidAsk
                 {
-                    // let bid_price = decode_f64(&mut fields_itr)?;
-                    // let ask_price = decode_f64(&mut fields_itr)?;
-                    // let bid_size = decode_i32(&mut fields_itr)?;
-                    // let ask_size = decode_i32(&mut fields_itr)?;
-                    // let mask = decode_i32(&mut fields_itr)?;
-//                    let mut tick_attrib_bid_ask = TickAttribBidAsk::default();
-//                    tick_attrib_bid_ask.bid_past_low = mask & 1 != 0;
-//                    tick_attrib_bid_ask.ask_past_high = mask & 2 != 0;
-//                    self.wrapper
-//                        .lock()
-//                        .expect(WRAPPER_POISONED_MUTEX)
-//                        .tick_by_tick_bid_ask(
-//                            req_id,
-//                            time,
-//                            bid_price,
-//                            ask_price,
-//                            bid_size,
-//                            ask_size,
-//                            tick_attrib_bid_ask,
-//                        );
+                    let bid_price = decode_f64(&mut fields_itr)?;
+                    let ask_price = decode_f64(&mut fields_itr)?;
+                    let bid_size = decode_i32(&mut fields_itr)?;
+                    let ask_size = decode_i32(&mut fields_itr)?;
+                    let mask = decode_i32(&mut fields_itr)?;
+
+                    let tick_bid_ask = TickBidAsk {
+                        time,
+                        bid_price,
+                        ask_price,
+                        bid_size,
+                        ask_size,
+                        tick_attrib_bid_ask: TickAttribBidAsk::from(mask)
+                    };
+
+                    sender.send(IncomingMessagesEnum::TickByTickBidAsk((req_id, tick_bid_ask)))?;
                 }
             4 =>
             // MidPoint
                 {
-                    // let mid_point = decode_f64(&mut fields_itr)?;
-//                    self.wrapper
-//                        .lock()
-//                        .expect(WRAPPER_POISONED_MUTEX)
-//                        .tick_by_tick_mid_point(req_id, time, mid_point);
+                    let mid_point = decode_f64(&mut fields_itr)?;
+
+                    let tick_mid_point = TickMidPoint {
+                        time,
+                        mid_point
+                    };
+
+                    sender.send(IncomingMessagesEnum::TickByTickMidPoint((req_id, tick_mid_point)))?;
                 }
             _ => return Ok(()),
         }
         Ok(())
     }
 
+    fn tick_option_computation_event(&mut self, server_version: i32, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let version = if server_version >= min_server_version::PRICE_BASED_VOLATILITY {
+            i32::MAX
+        } else {
+            decode_i32(&mut fields_itr)?
+        };
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let tick_type = TickType::from(decode_i32(&mut fields_itr)?);
+
+        let mut tick_attrib = None;
+        if server_version >= min_server_version::PRICE_BASED_VOLATILITY {
+            tick_attrib = match decode_i32(&mut fields_itr)? {
+                0 => Some(VolatilityBasis::ReturnBased),
+                1 => Some(VolatilityBasis::PriceBased),
+                _ => None
+            };
+        }
+
+        let mut computation = OptionComputation {
+            tick_attrib,
+            implied_vol: decode_option_value(&mut fields_itr, -1.0)?,
+            delta: decode_option_value(&mut fields_itr, -2.0)?,
+            opt_price: None,
+            pv_dividend: None,
+            gamma: None,
+            vega: None,
+            theta: None,
+            und_price: None,
+        };
+
+        if version >= 6 || tick_type == TickType::ModelOptionComputation || tick_type == TickType::DelayedModelOptionComputation {
+            computation.opt_price = decode_option_value(&mut fields_itr, -1.0)?;
+            computation.pv_dividend = decode_option_value(&mut fields_itr, -1.0)?;
+        }
+
+        if version >= 6 {
+            computation.gamma = decode_option_value(&mut fields_itr, -2.0)?;
+            computation.vega = decode_option_value(&mut fields_itr, -2.0)?;
+            computation.theta = decode_option_value(&mut fields_itr, -2.0)?;
+            computation.und_price = decode_option_value(&mut fields_itr, -1.0)?;
+        }
+
+        sender.send(IncomingMessagesEnum::TickOptionComputation(req_id, tick_type, computation))?;
+
+        Ok(())
+    }
+
     fn generic_tick(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
         let mut fields_itr = self.fields.iter();
 
@@ -785,7 +1237,7 @@ impl Decoder {
         fields_itr.next();
 
         let req_id = decode_i32(&mut fields_itr)?;
-        let tick_type = decode_i32(&mut fields_itr)?;
+        let tick_type = TickType::from(decode_i32(&mut fields_itr)?);
         let value = decode_f64(&mut fields_itr)?;
 
         sender.send(IncomingMessagesEnum::TickGeneric(req_id, tick_type, value))?;
@@ -793,28 +1245,975 @@ impl Decoder {
         Ok(())
     }
 
-    fn profit_and_losses(&mut self, server_version: i32, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+    fn tick_price_event(&mut self, server_version: i32, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
         let mut fields_itr = self.fields.iter();
 
         //throw away message_id
         fields_itr.next();
 
+        let version = decode_i32(&mut fields_itr)?;
         let req_id = decode_i32(&mut fields_itr)?;
-        let daily_pnl = decode_f64(&mut fields_itr)?;
-        let mut unrealized_pnl = 0.0;
-        let mut realized_pnl = 0.0;
+        let tick_type = TickType::from(decode_i32(&mut fields_itr)?);
+        let price = decode_f64(&mut fields_itr)?;
 
-        if server_version >= min_server_version::UNREALIZED_PNL {
-            unrealized_pnl = decode_f64(&mut fields_itr)?;
+        let mut size = 0.0;
+        if version >= 2 {
+            size = decode_f64(&mut fields_itr)?;
         }
 
-        if server_version >= min_server_version::REALIZED_PNL {
-            realized_pnl = decode_f64(&mut fields_itr)?;
+        let mut attrib = TickAttrib::default();
+        if version >= 3 {
+            let mask = decode_i32(&mut fields_itr)?;
+            attrib.can_auto_execute = mask == 1;
+
+            if server_version >= min_server_version::PAST_LIMIT {
+                attrib.can_auto_execute = mask & 1 != 0;
+                attrib.past_limit = mask & 2 != 0;
+
+                if server_version >= min_server_version::PRE_OPEN_BID_ASK {
+                    attrib.pre_open = mask & 4 != 0;
+                }
+            }
         }
 
-        sender.send(IncomingMessagesEnum::PnL(req_id, daily_pnl, unrealized_pnl, realized_pnl))?;
+        sender.send(IncomingMessagesEnum::TickPrice(req_id, tick_type, price, attrib))?;
+
+        // the size of a bid, ask or last price travels with the price tick, pass it on as its own size tick
+        if version >= 2 {
+            let size_tick_type = match tick_type {
+                TickType::Bid => Some(TickType::BidSize),
+                TickType::Ask => Some(TickType::AskSize),
+                TickType::Last => Some(TickType::LastSize),
+                TickType::DelayedBid => Some(TickType::DelayedBidSize),
+                TickType::DelayedAsk => Some(TickType::DelayedAskSize),
+                TickType::DelayedLast => Some(TickType::DelayedLastSize),
+                _ => None
+            };
+
+            if let Some(size_tick_type) = size_tick_type {
+                sender.send(IncomingMessagesEnum::TickSize(req_id, size_tick_type, size))?;
+            }
+        }
 
         Ok(())
     }
-}
 
+    fn tick_size_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+        //throw away version
+        fields_itr.next();
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let tick_type = TickType::from(decode_i32(&mut fields_itr)?);
+        let size = decode_f64(&mut fields_itr)?;
+
+        sender.send(IncomingMessagesEnum::TickSize(req_id, tick_type, size))?;
+
+        Ok(())
+    }
+
+    fn market_depth_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+        //throw away version
+        fields_itr.next();
+
+        let market_depth = MarketDepth {
+            req_id: decode_i32(&mut fields_itr)?,
+            position: decode_i32(&mut fields_itr)?,
+            market_maker: String::new(),
+            operation: MarketDepthOperation::from(decode_i32(&mut fields_itr)?),
+            side: MarketDepthSide::from(decode_i32(&mut fields_itr)?),
+            price: decode_f64(&mut fields_itr)?,
+            size: decode_f64(&mut fields_itr)?,
+            is_smart_depth: false,
+        };
+
+        sender.send(IncomingMessagesEnum::MarketDepth(market_depth))?;
+
+        Ok(())
+    }
+
+    fn market_depth_l2_event(&mut self, server_version: i32, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+        //throw away version
+        fields_itr.next();
+
+        let mut market_depth = MarketDepth {
+            req_id: decode_i32(&mut fields_itr)?,
+            position: decode_i32(&mut fields_itr)?,
+            market_maker: decode_string(&mut fields_itr)?,
+            operation: MarketDepthOperation::from(decode_i32(&mut fields_itr)?),
+            side: MarketDepthSide::from(decode_i32(&mut fields_itr)?),
+            price: decode_f64(&mut fields_itr)?,
+            size: decode_f64(&mut fields_itr)?,
+            is_smart_depth: false,
+        };
+
+        if server_version >= min_server_version::SMART_DEPTH {
+            market_depth.is_smart_depth = decode_bool(&mut fields_itr)?;
+        }
+
+        sender.send(IncomingMessagesEnum::MarketDepthL2(market_depth))?;
+
+        Ok(())
+    }
+
+    fn execution_data_event(&mut self, server_version: i32, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let version = if server_version < min_server_version::LAST_LIQUIDITY {
+            decode_i32(&mut fields_itr)?
+        }
+        else {
+            server_version
+        };
+
+        let mut req_id = -1;
+        if version >= 7 {
+            req_id = decode_i32(&mut fields_itr)?;
+        }
+
+        let mut execution = Execution::new();
+        execution.order_id = decode_i32(&mut fields_itr)?;
+
+        let mut contract = Contract::new();
+        if version >= 5 {
+            contract.con_id = decode_i32(&mut fields_itr)?;
+        }
+        contract.symbol = decode_string(&mut fields_itr)?;
+        contract.sec_type = decode_string(&mut fields_itr)?;
+        contract.last_trade_date_or_contract_month = decode_string(&mut fields_itr)?;
+        contract.strike = decode_f64(&mut fields_itr)?;
+        contract.right = decode_string(&mut fields_itr)?;
+        if version >= 9 {
+            contract.multiplier = decode_string(&mut fields_itr)?;
+        }
+        contract.exchange = decode_string(&mut fields_itr)?;
+        contract.currency = decode_string(&mut fields_itr)?;
+        contract.local_symbol = decode_string(&mut fields_itr)?;
+        if version >= 10 {
+            contract.trading_class = decode_string(&mut fields_itr)?;
+        }
+
+        execution.exec_id = decode_string(&mut fields_itr)?;
+        execution.time = decode_string(&mut fields_itr)?;
+        execution.acct_number = decode_string(&mut fields_itr)?;
+        execution.exchange = decode_string(&mut fields_itr)?;
+        execution.side = decode_string(&mut fields_itr)?;
+
+        if server_version >= min_server_version::FRACTIONAL_POSITIONS {
+            execution.shares = decode_f64(&mut fields_itr)?;
+        } else {
+            execution.shares = decode_i32(&mut fields_itr)? as f64;
+        }
+
+        execution.price = decode_f64(&mut fields_itr)?;
+
+        if version >= 2 {
+            execution.perm_id = decode_i32(&mut fields_itr)?;
+        }
+        if version >= 3 {
+            execution.client_id = decode_i32(&mut fields_itr)?;
+        }
+        if version >= 4 {
+            execution.liquidation = decode_i32(&mut fields_itr)?;
+        }
+        if version >= 6 {
+            if server_version >= min_server_version::FRACTIONAL_POSITIONS {
+                execution.cum_qty = decode_f64(&mut fields_itr)?;
+            } else {
+                execution.cum_qty = decode_i32(&mut fields_itr)? as f64;
+            }
+            execution.avg_price = decode_f64(&mut fields_itr)?;
+        }
+        if version >= 8 {
+            execution.order_ref = decode_string(&mut fields_itr)?;
+        }
+        if version >= 9 {
+            execution.ev_rule = decode_string(&mut fields_itr)?;
+            execution.ev_multiplier = decode_f64(&mut fields_itr)?;
+        }
+        if server_version >= min_server_version::MODELS_SUPPORT {
+            execution.model_code = decode_string(&mut fields_itr)?;
+        }
+        if server_version >= min_server_version::LAST_LIQUIDITY {
+            execution.last_liquidity = decode_i32(&mut fields_itr)?;
+        }
+        if server_version >= min_server_version::PENDING_PRICE_REVISION {
+            execution.pending_price_revision = decode_bool(&mut fields_itr)?;
+        }
+
+        sender.send(IncomingMessagesEnum::ExecutionData(req_id, contract, execution))?;
+
+        Ok(())
+    }
+
+    fn commission_report_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+        //throw away version
+        fields_itr.next();
+
+        let commission_report = CommissionReport {
+            exec_id: decode_string(&mut fields_itr)?,
+            commission: decode_f64(&mut fields_itr)?,
+            currency: decode_string(&mut fields_itr)?,
+            realized_pnl: decode_f64(&mut fields_itr)?,
+            yield_: decode_f64(&mut fields_itr)?,
+            yield_redemption_date: decode_i32(&mut fields_itr)?,
+        };
+
+        sender.send(IncomingMessagesEnum::CommissionsReport(commission_report))?;
+
+        Ok(())
+    }
+
+    fn account_value_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let version = decode_i32(&mut fields_itr)?;
+
+        let mut account_value = AccountValue {
+            key: decode_string(&mut fields_itr)?,
+            value: decode_string(&mut fields_itr)?,
+            currency: decode_string(&mut fields_itr)?,
+            account_name: "".to_string(),
+        };
+
+        if version >= 2 {
+            account_value.account_name = decode_string(&mut fields_itr)?;
+        }
+
+        sender.send(IncomingMessagesEnum::AccountValue(account_value))?;
+
+        Ok(())
+    }
+
+    fn portfolio_value_event(&mut self, server_version: i32, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let version = decode_i32(&mut fields_itr)?;
+
+        let mut contract = Contract::new();
+        if version >= 6 {
+            contract.con_id = decode_i32(&mut fields_itr)?;
+        }
+        contract.symbol = decode_string(&mut fields_itr)?;
+        contract.sec_type = decode_string(&mut fields_itr)?;
+        contract.last_trade_date_or_contract_month = decode_string(&mut fields_itr)?;
+        contract.strike = decode_f64(&mut fields_itr)?;
+        contract.right = decode_string(&mut fields_itr)?;
+        if version >= 7 {
+            contract.multiplier = decode_string(&mut fields_itr)?;
+            contract.primary_exchange = decode_string(&mut fields_itr)?;
+        }
+        contract.currency = decode_string(&mut fields_itr)?;
+        if version >= 2 {
+            contract.local_symbol = decode_string(&mut fields_itr)?;
+        }
+        if version >= 8 {
+            contract.trading_class = decode_string(&mut fields_itr)?;
+        }
+
+        let position = if server_version >= min_server_version::FRACTIONAL_POSITIONS {
+            decode_f64(&mut fields_itr)?
+        } else {
+            decode_i32(&mut fields_itr)? as f64
+        };
+
+        let market_price = decode_f64(&mut fields_itr)?;
+        let market_value = decode_f64(&mut fields_itr)?;
+
+        let mut average_cost = 0.0;
+        let mut unrealized_pnl = 0.0;
+        let mut realized_pnl = 0.0;
+        if version >= 3 {
+            average_cost = decode_f64(&mut fields_itr)?;
+            unrealized_pnl = decode_f64(&mut fields_itr)?;
+            realized_pnl = decode_f64(&mut fields_itr)?;
+        }
+
+        let mut account_name = "".to_string();
+        if version >= 4 {
+            account_name = decode_string(&mut fields_itr)?;
+        }
+
+        if version == 6 && server_version == 39 {
+            contract.primary_exchange = decode_string(&mut fields_itr)?;
+        }
+
+        sender.send(IncomingMessagesEnum::PortfolioValue(PortfolioItem {
+            contract,
+            position,
+            market_price,
+            market_value,
+            average_cost,
+            unrealized_pnl,
+            realized_pnl,
+            account_name,
+        }))?;
+
+        Ok(())
+    }
+
+    fn position_event(&mut self, server_version: i32, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let version = decode_i32(&mut fields_itr)?;
+        let account = decode_string(&mut fields_itr)?;
+
+        let mut contract = Contract::new();
+        contract.con_id = decode_i32(&mut fields_itr)?;
+        contract.symbol = decode_string(&mut fields_itr)?;
+        contract.sec_type = decode_string(&mut fields_itr)?;
+        contract.last_trade_date_or_contract_month = decode_string(&mut fields_itr)?;
+        contract.strike = decode_f64(&mut fields_itr)?;
+        contract.right = decode_string(&mut fields_itr)?;
+        contract.multiplier = decode_string(&mut fields_itr)?;
+        contract.exchange = decode_string(&mut fields_itr)?;
+        contract.currency = decode_string(&mut fields_itr)?;
+        contract.local_symbol = decode_string(&mut fields_itr)?;
+        if version >= 2 {
+            contract.trading_class = decode_string(&mut fields_itr)?;
+        }
+
+        let position = if server_version >= min_server_version::FRACTIONAL_POSITIONS {
+            decode_f64(&mut fields_itr)?
+        } else {
+            decode_i32(&mut fields_itr)? as f64
+        };
+
+        let mut avg_cost = 0.0;
+        if version >= 3 {
+            avg_cost = decode_f64(&mut fields_itr)?;
+        }
+
+        sender.send(IncomingMessagesEnum::Position(Position { account, contract, position, avg_cost }))?;
+
+        Ok(())
+    }
+
+    fn security_definition_option_parameter_event(&mut self, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let exchange = decode_string(&mut fields_itr)?;
+        let underlying_con_id = decode_i32(&mut fields_itr)?;
+        let trading_class = decode_string(&mut fields_itr)?;
+        let multiplier = decode_string(&mut fields_itr)?;
+
+        let expirations_count = decode_i32(&mut fields_itr)?;
+        let mut expirations = vec![];
+        for _ in 0..expirations_count {
+            expirations.push(decode_string(&mut fields_itr)?);
+        }
+
+        let strikes_count = decode_i32(&mut fields_itr)?;
+        let mut strikes = vec![];
+        for _ in 0..strikes_count {
+            strikes.push(decode_f64(&mut fields_itr)?);
+        }
+
+        sender.send(IncomingMessagesEnum::SecurityDefinitionOptionParameter(req_id, OptionChain {
+            exchange,
+            underlying_con_id,
+            trading_class,
+            multiplier,
+            expirations,
+            strikes,
+        }))?;
+
+        Ok(())
+    }
+
+    fn profit_and_losses(&mut self, server_version: i32, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let daily_pnl = decode_f64(&mut fields_itr)?;
+        let mut unrealized_pnl = 0.0;
+        let mut realized_pnl = 0.0;
+
+        if server_version >= min_server_version::UNREALIZED_PNL {
+            unrealized_pnl = decode_f64(&mut fields_itr)?;
+        }
+
+        if server_version >= min_server_version::REALIZED_PNL {
+            realized_pnl = decode_f64(&mut fields_itr)?;
+        }
+
+        sender.send(IncomingMessagesEnum::PnL(PnL {
+            req_id,
+            daily_pnl,
+            unrealized_pnl,
+            realized_pnl,
+        }))?;
+
+        Ok(())
+    }
+
+    fn profit_and_losses_single(&mut self, server_version: i32, sender: &Sender<IncomingMessagesEnum>) -> Result<(), Box<dyn Error>> {
+        let mut fields_itr = self.fields.iter();
+
+        //throw away message_id
+        fields_itr.next();
+
+        let req_id = decode_i32(&mut fields_itr)?;
+        let position = decode_f64(&mut fields_itr)?;
+        let daily_pnl = decode_f64(&mut fields_itr)?;
+        let mut unrealized_pnl = 0.0;
+        let mut realized_pnl = 0.0;
+
+        if server_version >= min_server_version::UNREALIZED_PNL {
+            unrealized_pnl = decode_f64(&mut fields_itr)?;
+        }
+
+        if server_version >= min_server_version::REALIZED_PNL {
+            realized_pnl = decode_f64(&mut fields_itr)?;
+        }
+
+        let value = decode_f64(&mut fields_itr)?;
+
+        sender.send(IncomingMessagesEnum::PnLSingle(PnLSingle {
+            req_id,
+            position,
+            daily_pnl,
+            unrealized_pnl,
+            realized_pnl,
+            value,
+        }))?;
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::unbounded;
+
+    fn decode(fields: &[&str], server_version: i32) -> Vec<IncomingMessagesEnum> {
+        let fields: Vec<String> = fields.iter().map(|field| field.to_string()).collect();
+        let (sender, receiver) = unbounded();
+
+        Decoder::new(fields.as_slice()).process_incoming_message(server_version, &sender).unwrap();
+
+        receiver.try_iter().collect()
+    }
+
+    #[test]
+    fn tick_price_is_followed_by_its_size() {
+        let events = decode(&["1", "6", "7", "1", "185.25", "300", "3"], 151);
+
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            IncomingMessagesEnum::TickPrice(req_id, tick_type, price, attrib) => {
+                assert_eq!((*req_id, *tick_type, *price), (7, TickType::Bid, 185.25));
+                assert!(attrib.can_auto_execute);
+                assert!(attrib.past_limit);
+                assert!(!attrib.pre_open);
+            },
+            _ => panic!("expected TickPrice"),
+        }
+        match &events[1] {
+            IncomingMessagesEnum::TickSize(req_id, tick_type, size) => assert_eq!((*req_id, *tick_type, *size), (7, TickType::BidSize, 300.0)),
+            _ => panic!("expected TickSize"),
+        }
+    }
+
+    #[test]
+    fn tick_price_attrib_before_past_limit_support() {
+        let events = decode(&["1", "6", "7", "9", "185.25", "0", "3"], 100);
+
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            IncomingMessagesEnum::TickPrice(_, _, _, attrib) => {
+                assert!(!attrib.can_auto_execute);
+                assert!(!attrib.past_limit);
+            },
+            _ => panic!("expected TickPrice"),
+        }
+    }
+
+    #[test]
+    fn delayed_tick_price_is_followed_by_delayed_size() {
+        let events = decode(&["1", "6", "7", "68", "185.25", "12", "0"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::TickPrice(_, TickType::DelayedLast, _, _), IncomingMessagesEnum::TickSize(_, TickType::DelayedLastSize, size)] => assert_eq!(*size, 12.0),
+            _ => panic!("expected DelayedLast price and DelayedLastSize"),
+        }
+    }
+
+    #[test]
+    fn tick_by_tick_last() {
+        let events = decode(&["99", "7", "1", "1652198400", "185.25", "100", "2", "ISLAND", "T"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::TickByTickLast((7, tick))] => {
+                assert_eq!((tick.time, tick.price, tick.size), (1652198400, 185.25, 100));
+                assert!(!tick.tick_attrib_last.past_limit);
+                assert!(tick.tick_attrib_last.unreported);
+                assert_eq!(tick.exchange, "ISLAND");
+            },
+            _ => panic!("expected a single TickByTickLast"),
+        }
+    }
+
+    #[test]
+    fn tick_by_tick_bid_ask() {
+        let events = decode(&["99", "7", "3", "1652198400", "185.2", "185.3", "300", "400", "1"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::TickByTickBidAsk((7, tick))] => {
+                assert_eq!((tick.bid_price, tick.ask_price, tick.bid_size, tick.ask_size), (185.2, 185.3, 300, 400));
+                assert!(tick.tick_attrib_bid_ask.bid_past_low);
+                assert!(!tick.tick_attrib_bid_ask.ask_past_high);
+            },
+            _ => panic!("expected a single TickByTickBidAsk"),
+        }
+    }
+
+    #[test]
+    fn tick_by_tick_mid_point() {
+        let events = decode(&["99", "7", "4", "1652198400", "185.25"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::TickByTickMidPoint((7, tick))] => assert_eq!((tick.time, tick.mid_point), (1652198400, 185.25)),
+            _ => panic!("expected a single TickByTickMidPoint"),
+        }
+    }
+
+    #[test]
+    fn tick_size() {
+        let events = decode(&["2", "6", "7", "8", "1200"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::TickSize(req_id, tick_type, size)] => assert_eq!((*req_id, *tick_type, *size), (7, TickType::Volume, 1200.0)),
+            _ => panic!("expected a single TickSize"),
+        }
+    }
+
+    #[test]
+    fn market_depth() {
+        let events = decode(&["12", "1", "7", "0", "1", "1", "185.25", "300"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::MarketDepth(depth)] => {
+                assert_eq!((depth.req_id, depth.position, depth.price, depth.size), (7, 0, 185.25, 300.0));
+                assert_eq!((depth.operation, depth.side), (MarketDepthOperation::Update, MarketDepthSide::Bid));
+                assert!(depth.market_maker.is_empty());
+            },
+            _ => panic!("expected a single MarketDepth"),
+        }
+    }
+
+    #[test]
+    fn market_depth_l2_with_smart_depth() {
+        let events = decode(&["13", "1", "7", "2", "ARCA", "0", "0", "185.3", "400", "1"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::MarketDepthL2(depth)] => {
+                assert_eq!((depth.req_id, depth.position, depth.market_maker.as_str()), (7, 2, "ARCA"));
+                assert_eq!((depth.operation, depth.side), (MarketDepthOperation::Insert, MarketDepthSide::Ask));
+                assert_eq!((depth.price, depth.size), (185.3, 400.0));
+                assert!(depth.is_smart_depth);
+            },
+            _ => panic!("expected a single MarketDepthL2"),
+        }
+    }
+
+    #[test]
+    fn execution_data() {
+        let events = decode(&["11", "3", "42", "265598", "AAPL", "STK", "", "0", "", "", "ISLAND", "USD", "AAPL", "NMS",
+            "0000e0d5.6290ab1c.01.01", "20220510  09:30:01", "DU123456", "ISLAND", "BOT", "100", "185.25", "1234567", "0", "0",
+            "100", "185.25", "my ref", "", "", "", "2"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::ExecutionData(req_id, contract, execution)] => {
+                assert_eq!((*req_id, execution.order_id), (3, 42));
+                assert_eq!((contract.con_id, contract.symbol.as_str(), contract.trading_class.as_str()), (265598, "AAPL", "NMS"));
+                assert_eq!((execution.exec_id.as_str(), execution.side.as_str()), ("0000e0d5.6290ab1c.01.01", "BOT"));
+                assert_eq!((execution.shares, execution.price, execution.cum_qty, execution.avg_price), (100.0, 185.25, 100.0, 185.25));
+                assert_eq!((execution.perm_id, execution.order_ref.as_str()), (1234567, "my ref"));
+                assert_eq!(execution.last_liquidity, 2);
+                assert!(!execution.pending_price_revision);
+            },
+            _ => panic!("expected a single ExecutionData"),
+        }
+    }
+
+    #[test]
+    fn commission_report() {
+        let events = decode(&["59", "1", "0000e0d5.6290ab1c.01.01", "1.0", "USD", "1.7976931348623157E308", "1.7976931348623157E308", "0"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::CommissionsReport(report)] => {
+                assert_eq!((report.exec_id.as_str(), report.commission, report.currency.as_str()), ("0000e0d5.6290ab1c.01.01", 1.0, "USD"));
+                assert_eq!(report.realized_pnl, f64::MAX);
+            },
+            _ => panic!("expected a single CommissionsReport"),
+        }
+    }
+
+    #[test]
+    fn portfolio_value() {
+        let events = decode(&["7", "8", "265598", "AAPL", "STK", "", "0", "", "", "NASDAQ", "USD", "AAPL", "NMS",
+            "100", "185.25", "18525", "150.5", "3475", "12.5", "DU123456"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::PortfolioValue(item)] => {
+                assert_eq!((item.contract.con_id, item.contract.primary_exchange.as_str(), item.contract.trading_class.as_str()), (265598, "NASDAQ", "NMS"));
+                assert_eq!((item.position, item.market_price, item.market_value), (100.0, 185.25, 18525.0));
+                assert_eq!((item.average_cost, item.unrealized_pnl, item.realized_pnl), (150.5, 3475.0, 12.5));
+                assert_eq!(item.account_name, "DU123456");
+            },
+            _ => panic!("expected a single PortfolioValue"),
+        }
+    }
+
+    #[test]
+    fn position() {
+        let events = decode(&["61", "3", "DU123456", "265598", "AAPL", "STK", "", "0", "", "", "NASDAQ", "USD", "AAPL", "NMS", "-25", "150.5"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::Position(position)] => {
+                assert_eq!((position.account.as_str(), position.contract.symbol.as_str(), position.contract.exchange.as_str()), ("DU123456", "AAPL", "NASDAQ"));
+                assert_eq!((position.position, position.avg_cost), (-25.0, 150.5));
+            },
+            _ => panic!("expected a single Position"),
+        }
+    }
+
+    #[test]
+    fn security_definition_option_parameter() {
+        let events = decode(&["75", "9", "SMART", "265598", "AAPL", "100", "2", "20220520", "20220617", "3", "180", "185", "190"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::SecurityDefinitionOptionParameter(9, chain)] => {
+                assert_eq!((chain.exchange.as_str(), chain.underlying_con_id, chain.trading_class.as_str(), chain.multiplier.as_str()), ("SMART", 265598, "AAPL", "100"));
+                assert_eq!(chain.expirations, vec!["20220520", "20220617"]);
+                assert_eq!(chain.strikes, vec![180.0, 185.0, 190.0]);
+            },
+            _ => panic!("expected a single SecurityDefinitionOptionParameter"),
+        }
+    }
+
+    #[test]
+    fn tick_option_computation() {
+        let events = decode(&["21", "6", "7", "13", "0.25", "0.55", "4.2", "-1", "0.04", "0.12", "-0.05", "185.25"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::TickOptionComputation(7, TickType::ModelOptionComputation, computation)] => {
+                assert_eq!(computation.tick_attrib, None);
+                assert_eq!((computation.implied_vol, computation.delta), (Some(0.25), Some(0.55)));
+                assert_eq!((computation.opt_price, computation.pv_dividend), (Some(4.2), None));
+                assert_eq!((computation.gamma, computation.vega, computation.theta), (Some(0.04), Some(0.12), Some(-0.05)));
+                assert_eq!(computation.und_price, Some(185.25));
+            },
+            _ => panic!("expected a single TickOptionComputation"),
+        }
+    }
+
+    #[test]
+    fn tick_option_computation_not_computed() {
+        let events = decode(&["21", "6", "7", "10", "-1", "-2", "-1", "-1", "-2", "-2", "-2", "-1"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::TickOptionComputation(7, TickType::BidOptionComputation, computation)] => {
+                assert_eq!((computation.implied_vol, computation.delta, computation.opt_price, computation.pv_dividend), (None, None, None, None));
+                assert_eq!((computation.gamma, computation.vega, computation.theta, computation.und_price), (None, None, None, None));
+            },
+            _ => panic!("expected a single TickOptionComputation"),
+        }
+    }
+
+    #[test]
+    fn tick_option_computation_with_volatility_basis() {
+        let events = decode(&["21", "7", "12", "1", "0.3", "-0.45", "3.1", "0", "0.02", "0.1", "-0.04", "180"], 156);
+
+        match &events[..] {
+            [IncomingMessagesEnum::TickOptionComputation(7, TickType::LastOptionComputation, computation)] => {
+                assert_eq!(computation.tick_attrib, Some(VolatilityBasis::PriceBased));
+                assert_eq!((computation.implied_vol, computation.delta, computation.und_price), (Some(0.3), Some(-0.45), Some(180.0)));
+            },
+            _ => panic!("expected a single TickOptionComputation"),
+        }
+    }
+
+    #[test]
+    fn historical_data_bars_carry_req_id() {
+        let events = decode(&["17", "4", "20220503  09:30:00", "20220503  16:00:00", "2",
+            "20220503  09:30:00", "185.0", "186.0", "184.5", "185.5", "1200", "185.4", "30",
+            "20220503  09:31:00", "185.5", "185.9", "185.1", "185.2", "800", "185.6", "21"], 151);
+
+        assert_eq!(events.len(), 3);
+        match &events[1] {
+            IncomingMessagesEnum::HistoricalData(4, bar) => assert_eq!((bar.time_str.as_str(), bar.close, bar.volume, bar.count), ("20220503  09:31:00", 185.2, 800.0, 21.0)),
+            _ => panic!("expected HistoricalData"),
+        }
+        match &events[2] {
+            IncomingMessagesEnum::HistoricalDataEnd(4, start, end) => assert_eq!((start.as_str(), end.as_str()), ("20220503  09:30:00", "20220503  16:00:00")),
+            _ => panic!("expected HistoricalDataEnd"),
+        }
+    }
+
+    #[test]
+    fn historical_bars_use_the_registered_time_zone() {
+        let fields: Vec<String> = ["17", "4", "", "", "1", "20220503  09:30:00", "185.0", "186.0", "184.5", "185.5", "1200", "185.4", "30"]
+            .iter().map(|field| field.to_string()).collect();
+        let time_zones: TimeZones = Arc::new(Mutex::new(HashMap::new()));
+        time_zones.lock().unwrap().insert(4, "US/Eastern".to_string());
+        let (sender, receiver) = unbounded();
+
+        Decoder::new(fields.as_slice()).with_time_zones(&time_zones).process_incoming_message(151, &sender).unwrap();
+
+        match receiver.try_iter().next() {
+            Some(IncomingMessagesEnum::HistoricalData(4, bar)) => assert_eq!((bar.time_int, bar.has_time), (1651584600, true)),
+            _ => panic!("expected HistoricalData"),
+        }
+        assert!(decode(&["17", "5", "", "", "1", "20220503  09:30:00", "185.0", "186.0", "184.5", "185.5", "1200", "185.4", "30"], 151).iter()
+            .all(|event| !matches!(event, IncomingMessagesEnum::HistoricalData(_, bar) if bar.has_time)));
+    }
+
+    #[test]
+    fn real_time_bar_time() {
+        let events = decode(&["50", "3", "5", "1651582800", "185.0", "186.0", "184.5", "185.5", "1200", "185.4", "30"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::RealTimeBars(5, bar)] => assert_eq!((bar.time_int, bar.close, bar.count), (1651582800, 185.5, 30.0)),
+            _ => panic!("expected a single RealTimeBars"),
+        }
+    }
+
+    #[test]
+    fn historical_data_update() {
+        let events = decode(&["90", "4", "12", "20220503  09:32:00", "185.2", "185.7", "185.8", "185.1", "185.45", "640"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::HistoricalDataUpdate(4, bar)] => {
+                assert_eq!(bar.time_str, "20220503  09:32:00");
+                assert_eq!((bar.open, bar.high, bar.low, bar.close), (185.2, 185.8, 185.1, 185.7));
+                assert_eq!((bar.wap, bar.volume, bar.count), (185.45, 640.0, 12.0));
+            },
+            _ => panic!("expected a single HistoricalDataUpdate"),
+        }
+    }
+
+    #[test]
+    fn historical_ticks_midpoint() {
+        let events = decode(&["96", "8", "2", "1651582800", "0", "185.25", "0", "1651582801", "0", "185.3", "0", "1"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::HistoricalTick(8, ticks, true)] => {
+                assert_eq!(ticks.iter().map(|tick| (tick.time, tick.price)).collect::<Vec<_>>(), vec![(1651582800, 185.25), (1651582801, 185.3)]);
+            },
+            _ => panic!("expected a single HistoricalTick"),
+        }
+    }
+
+    #[test]
+    fn historical_ticks_bid_ask() {
+        let events = decode(&["97", "8", "1", "1651582800", "1", "185.2", "185.3", "300", "400", "0"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::HistoricalTickBidAsk(8, ticks, false)] => {
+                assert_eq!((ticks[0].bid_price, ticks[0].ask_price, ticks[0].bid_size, ticks[0].ask_size), (185.2, 185.3, 300, 400));
+                assert!(!ticks[0].tick_attrib_bid_ask.bid_past_low);
+                assert!(ticks[0].tick_attrib_bid_ask.ask_past_high);
+            },
+            _ => panic!("expected a single HistoricalTickBidAsk"),
+        }
+    }
+
+    #[test]
+    fn historical_ticks_last() {
+        let events = decode(&["98", "8", "2", "1651582800", "0", "185.25", "100", "ISLAND", "", "1651582801", "2", "185.3", "5", "FINRA", "I", "1"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::HistoricalTickLast(8, ticks, true)] => {
+                assert_eq!(ticks.len(), 2);
+                assert_eq!((ticks[0].price, ticks[0].size, ticks[0].exchange.as_str()), (185.25, 100, "ISLAND"));
+                assert_eq!((ticks[1].special_conditions.as_str(), ticks[1].tick_attrib_last.unreported), ("I", true));
+            },
+            _ => panic!("expected a single HistoricalTickLast"),
+        }
+    }
+
+    #[test]
+    fn head_timestamp() {
+        let events = decode(&["88", "5", "19801212  14:30:00"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::HeadTimestamp(5, head_timestamp)] => assert_eq!(head_timestamp, "19801212  14:30:00"),
+            _ => panic!("expected a single HeadTimestamp"),
+        }
+    }
+
+    #[test]
+    fn histogram_data() {
+        let events = decode(&["89", "5", "2", "185.25", "12000", "185.5", "800"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::HistogramData(5, entries)] => {
+                assert_eq!(entries, &vec![HistogramEntry { price: 185.25, size: 12000 }, HistogramEntry { price: 185.5, size: 800 }]);
+            },
+            _ => panic!("expected a single HistogramData"),
+        }
+    }
+
+    #[test]
+    fn pnl() {
+        let events = decode(&["94", "12", "-150.25", "320.5", "75"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::PnL(pnl)] => {
+                assert_eq!(pnl, &PnL { req_id: 12, daily_pnl: -150.25, unrealized_pnl: 320.5, realized_pnl: 75.0 });
+            },
+            _ => panic!("expected a single PnL"),
+        }
+    }
+
+    #[test]
+    fn pnl_single() {
+        let events = decode(&["95", "13", "100", "-42.5", "1200", "1.7976931348623157E308", "18550"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::PnLSingle(pnl)] => {
+                assert_eq!((pnl.req_id, pnl.position, pnl.daily_pnl, pnl.unrealized_pnl), (13, 100.0, -42.5, 1200.0));
+                assert_eq!((pnl.realized_pnl, pnl.value), (f64::MAX, 18550.0));
+            },
+            _ => panic!("expected a single PnLSingle"),
+        }
+    }
+
+    #[test]
+    fn completed_order() {
+        let events = decode(&[
+            "101",
+            // contract
+            "265598", "AAPL", "STK", "", "0", "?", "", "SMART", "USD", "AAPL", "NMS",
+            // action, quantity, type, limit, aux, tif, oca group, account, open/close, origin, ref
+            "BUY", "100", "LMT", "185.5", "", "DAY", "", "DU123456", "", "0", "audit",
+            // perm id, outside rth, hidden, discretionary amount, good after time
+            "1234567", "0", "0", "0", "",
+            // fa params, model code, good till date, rule 80A, percent offset, settling firm
+            "", "", "", "", "", "", "", "", "",
+            // short sale, box, peg to stock, display size, sweep, all or none, min qty, oca type, trigger
+            "0", "", "-1", "", "", "", "", "", "0", "0", "0", "", "3", "0",
+            // volatility with a delta neutral order type
+            "", "0", "None", "", "0", "0", "0", "", "0", "0",
+            // trail, combo legs, smart combo routing, scale, hedge, clearing, not held, delta neutral, algo, solicited
+            "", "", "", "0", "0", "0", "", "", "", "", "", "", "0", "0", "", "0",
+            // status, randomize flags, conditions, stop price and limit offset, cash qty, auto price, oms container
+            "Filled", "0", "0", "0", "", "", "", "0", "0",
+            // auto cancel date, filled quantity, ref futures con id, auto cancel parent, shareholder,
+            // imbalance only, route marketable to bbo, parent perm id
+            "", "100", "0", "0", "", "0", "0", "7654321",
+            "20221018 10:15:02 America/New_York", "Filled Size: 100",
+        ], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::CompletedOrder(item)] => {
+                assert_eq!((item.contract.con_id, item.contract.symbol.as_str(), item.contract.trading_class.as_str()), (265598, "AAPL", "NMS"));
+                assert_eq!((item.order.action.as_str(), item.order.total_quantity, item.order.lmt_price), ("BUY", 100.0, 185.5));
+                assert_eq!((item.order.account.as_str(), item.order.order_ref.as_str(), item.order.perm_id), ("DU123456", "audit", 1234567));
+                assert_eq!((item.order.oca_type, item.order.delta_neutral_order_type.as_str()), (3, "None"));
+                assert_eq!((item.order.filled_quantity, item.order.parent_perm_id), (100.0, 7654321));
+                assert_eq!(item.order_state.status, "Filled");
+                assert_eq!(item.order_state.completed_time, "20221018 10:15:02 America/New_York");
+                assert_eq!(item.order_state.completed_status, "Filled Size: 100");
+            },
+            _ => panic!("expected a single CompletedOrder"),
+        }
+    }
+
+    #[test]
+    fn completed_orders_end() {
+        let events = decode(&["102"], 151);
+
+        assert!(matches!(events[..], [IncomingMessagesEnum::CompletedOrdersEnd]));
+    }
+
+    #[test]
+    fn open_order() {
+        let events = decode(&[
+            "5",
+            // order id and contract
+            "17", "265598", "AAPL", "STK", "", "0", "?", "", "SMART", "USD", "AAPL", "NMS",
+            // action, quantity, type, limit, aux, tif, oca group, account, open/close, origin, ref, client id, perm id
+            "SELL", "50", "LMT", "190.25", "", "GTC", "", "DU123456", "", "0", "", "2", "1234568",
+            // outside rth, hidden, discretionary amount, good after time, shares allocation
+            "1", "0", "0", "", "",
+            // fa params, model code, good till date, rule 80A, percent offset, settling firm
+            "", "", "", "", "", "", "", "", "",
+            // short sale, auction strategy, box, peg to stock, display size
+            "0", "", "-1", "0", "", "", "", "", "", "0",
+            // block, sweep, all or none, min qty, oca type, etrade only, firm quote only, nbbo cap, parent id, trigger
+            "0", "0", "0", "", "3", "0", "0", "", "0", "0",
+            // volatility without a delta neutral order type
+            "", "0", "", "", "0", "0",
+            // trail, basis points, combo legs, smart combo routing, scale, hedge, opt out, clearing, not held, delta neutral, algo, solicited
+            "", "", "", "", "", "0", "0", "0", "", "", "", "", "0", "", "", "0", "0", "", "0",
+            // what if, status, margins, commissions, warning
+            "0", "Submitted", "", "", "", "", "", "", "", "", "", "", "", "", "", "",
+            // randomize flags, conditions, adjusted order, soft dollar tier
+            "0", "0", "0", "", "", "", "", "", "", "", "0", "", "", "",
+            // cash qty, auto price for hedge, oms container, discretionary up to limit, price management algo
+            "", "0", "0", "0", "1",
+        ], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::OpenOrder(item)] => {
+                assert_eq!((item.order_id, item.order.order_id, item.contract.symbol.as_str()), (17, 17, "AAPL"));
+                assert_eq!((item.order.action.as_str(), item.order.total_quantity, item.order.lmt_price), ("SELL", 50.0, 190.25));
+                assert_eq!((item.order.client_id, item.order.perm_id, item.order.outside_rth), (2, 1234568, true));
+                assert_eq!(item.order_state.status, "Submitted");
+                assert!(item.order.use_price_mgmt_algo);
+            },
+            _ => panic!("expected a single OpenOrder"),
+        }
+    }
+
+    #[test]
+    fn order_bound() {
+        let events = decode(&["100", "1234569", "0", "-5"], 151);
+
+        match &events[..] {
+            [IncomingMessagesEnum::OrderBound(bound)] => {
+                assert_eq!(bound, &OrderBound { perm_id: 1234569, api_client_id: 0, api_order_id: -5 });
+            },
+            _ => panic!("expected a single OrderBound"),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use core::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AccountSummaryTag {
+    AccountType,
+    NetLiquidation,
+    TotalCashValue,
+    SettledCash,
+    AccruedCash,
+    BuyingPower,
+    EquityWithLoanValue,
+    PreviousEquityWithLoanValue,
+    GrossPositionValue,
+    RegTEquity,
+    RegTMargin,
+    Sma,
+    InitMarginReq,
+    MaintMarginReq,
+    AvailableFunds,
+    ExcessLiquidity,
+    Cushion,
+    FullInitMarginReq,
+    FullMaintMarginReq,
+    FullAvailableFunds,
+    FullExcessLiquidity,
+    LookAheadNextChange,
+    LookAheadInitMarginReq,
+    LookAheadMaintMarginReq,
+    LookAheadAvailableFunds,
+    LookAheadExcessLiquidity,
+    HighestSeverity,
+    DayTradesRemaining,
+    Leverage,
+    // cash balances in the base currency ($LEDGER)
+    Ledger,
+    // cash balances in every currency held ($LEDGER:ALL)
+    LedgerAll,
+    // cash balances in a single currency, e.g. $LEDGER:EUR
+    LedgerCurrency(String),
+}
+
+impl AccountSummaryTag {
+    // Every tag except the ledger ones, which are a different kind of request.
+    pub fn all() -> Vec<AccountSummaryTag> {
+        vec![
+            AccountSummaryTag::AccountType,
+            AccountSummaryTag::NetLiquidation,
+            AccountSummaryTag::TotalCashValue,
+            AccountSummaryTag::SettledCash,
+            AccountSummaryTag::AccruedCash,
+            AccountSummaryTag::BuyingPower,
+            AccountSummaryTag::EquityWithLoanValue,
+            AccountSummaryTag::PreviousEquityWithLoanValue,
+            AccountSummaryTag::GrossPositionValue,
+            AccountSummaryTag::RegTEquity,
+            AccountSummaryTag::RegTMargin,
+            AccountSummaryTag::Sma,
+            AccountSummaryTag::InitMarginReq,
+            AccountSummaryTag::MaintMarginReq,
+            AccountSummaryTag::AvailableFunds,
+            AccountSummaryTag::ExcessLiquidity,
+            AccountSummaryTag::Cushion,
+            AccountSummaryTag::FullInitMarginReq,
+            AccountSummaryTag::FullMaintMarginReq,
+            AccountSummaryTag::FullAvailableFunds,
+            AccountSummaryTag::FullExcessLiquidity,
+            AccountSummaryTag::LookAheadNextChange,
+            AccountSummaryTag::LookAheadInitMarginReq,
+            AccountSummaryTag::LookAheadMaintMarginReq,
+            AccountSummaryTag::LookAheadAvailableFunds,
+            AccountSummaryTag::LookAheadExcessLiquidity,
+            AccountSummaryTag::HighestSeverity,
+            AccountSummaryTag::DayTradesRemaining,
+            AccountSummaryTag::Leverage,
+        ]
+    }
+
+    // The comma separated list expected by reqAccountSummary.
+    pub fn join(tags: &[AccountSummaryTag]) -> String {
+        tags.iter().map(|tag| tag.to_string()).collect::<Vec<String>>().join(",")
+    }
+}
+
+impl fmt::Display for AccountSummaryTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AccountSummaryTag::AccountType => "AccountType",
+            AccountSummaryTag::NetLiquidation => "NetLiquidation",
+            AccountSummaryTag::TotalCashValue => "TotalCashValue",
+            AccountSummaryTag::SettledCash => "SettledCash",
+            AccountSummaryTag::AccruedCash => "AccruedCash",
+            AccountSummaryTag::BuyingPower => "BuyingPower",
+            AccountSummaryTag::EquityWithLoanValue => "EquityWithLoanValue",
+            AccountSummaryTag::PreviousEquityWithLoanValue => "PreviousEquityWithLoanValue",
+            AccountSummaryTag::GrossPositionValue => "GrossPositionValue",
+            AccountSummaryTag::RegTEquity => "RegTEquity",
+            AccountSummaryTag::RegTMargin => "RegTMargin",
+            AccountSummaryTag::Sma => "SMA",
+            AccountSummaryTag::InitMarginReq => "InitMarginReq",
+            AccountSummaryTag::MaintMarginReq => "MaintMarginReq",
+            AccountSummaryTag::AvailableFunds => "AvailableFunds",
+            AccountSummaryTag::ExcessLiquidity => "ExcessLiquidity",
+            AccountSummaryTag::Cushion => "Cushion",
+            AccountSummaryTag::FullInitMarginReq => "FullInitMarginReq",
+            AccountSummaryTag::FullMaintMarginReq => "FullMaintMarginReq",
+            AccountSummaryTag::FullAvailableFunds => "FullAvailableFunds",
+            AccountSummaryTag::FullExcessLiquidity => "FullExcessLiquidity",
+            AccountSummaryTag::LookAheadNextChange => "LookAheadNextChange",
+            AccountSummaryTag::LookAheadInitMarginReq => "LookAheadInitMarginReq",
+            AccountSummaryTag::LookAheadMaintMarginReq => "LookAheadMaintMarginReq",
+            AccountSummaryTag::LookAheadAvailableFunds => "LookAheadAvailableFunds",
+            AccountSummaryTag::LookAheadExcessLiquidity => "LookAheadExcessLiquidity",
+            AccountSummaryTag::HighestSeverity => "HighestSeverity",
+            AccountSummaryTag::DayTradesRemaining => "DayTradesRemaining",
+            AccountSummaryTag::Leverage => "Leverage",
+            AccountSummaryTag::Ledger => "$LEDGER",
+            AccountSummaryTag::LedgerAll => "$LEDGER:ALL",
+            AccountSummaryTag::LedgerCurrency(currency) => return write!(f, "$LEDGER:{}", currency),
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,8 @@
+// The order an attached stop turns into once its trigger price is reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdjustedStop {
+    Stop { stop_price: f64 },
+    StopLimit { stop_price: f64, limit_price: f64 },
+    TrailingAmount { stop_price: f64, trailing_amount: f64 },
+    TrailingPercent { stop_price: f64, trailing_percent: f64 },
+}
@@ -0,0 +1,5 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExerciseAction {
+    Exercise = 1,
+    Lapse = 2,
+}
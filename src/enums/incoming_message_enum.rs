@@ -5,32 +5,50 @@ use crate::models::contract_details::ContractDetails;
 use crate::models::order_status_message::OrderStatusMessage;
 use crate::models::bar::Bar;
 use crate::models::tick_last::TickLast;
+use crate::models::tick_bid_ask::TickBidAsk;
+use crate::models::tick_mid_point::TickMidPoint;
+use crate::models::tick_attrib::TickAttrib;
+use crate::enums::tick_type::TickType;
+use crate::models::market_depth::MarketDepth;
+use crate::models::contract::Contract;
+use crate::models::execution::Execution;
+use crate::models::commission_report::CommissionReport;
+use crate::models::position::Position;
+use crate::models::portfolio_item::PortfolioItem;
+use crate::models::account_value::AccountValue;
+use crate::models::option_chain::OptionChain;
+use crate::models::option_computation::OptionComputation;
+use crate::models::historical_tick::HistoricalTick;
+use crate::models::histogram_entry::HistogramEntry;
+use crate::models::pnl::PnL;
+use crate::models::pnl_single::PnLSingle;
+use crate::models::order_bound::OrderBound;
 
 pub enum IncomingMessagesEnum {
     NotValid,
-    TickPrice,
-    TickSize,
+    TickPrice(i32, TickType, f64, TickAttrib),
+    TickSize(i32, TickType, f64),
     OrderStatus(OrderStatusMessage),
     Error(i32, i32, String),
     OpenOrder(OrderDataItem),
-    AccountValue,
-    PortfolioValue,
-    AccountUpdateTime,
+    AccountValue(AccountValue),
+    PortfolioValue(PortfolioItem),
+    AccountUpdateTime(String),
     NextValidId(i32),
     ContractData(i32, ContractDetails),
-    ExecutionData,
-    MarketDepth,
-    MarketDepthL2,
+    ExecutionData(i32, Contract, Execution),
+    MarketDepth(MarketDepth),
+    MarketDepthL2(MarketDepth),
     NewsBulletins,
     ManagedAccounts(String),
     ReceiveFa,
-    HistoricalData(Bar),
+    HistoricalData(i32, Bar),
     HistoricalDataEnd(i32, String, String),
     BondContractData,
     ScannerParameters,
     ScannerData(Vec<ScanDataItem>),
-    TickOptionComputation,
-    TickGeneric(i32, i32, f64),
+    TickOptionComputation(i32, TickType, OptionComputation),
+    TickGeneric(i32, TickType, f64),
     TickString,
     TickEfp,//TICK EFP 47
     CurrentTime,
@@ -38,13 +56,13 @@ pub enum IncomingMessagesEnum {
     FundamentalData,
     ContractDataEnd(i32),
     OpenOrderEnd,
-    AccountDownloadEnd,
-    ExecutionDataEnd,
+    AccountDownloadEnd(String),
+    ExecutionDataEnd(i32),
     DeltaNeutralValidation,
     TickSnapshotEnd,
     MarketDataType,
-    CommissionsReport,
-    Position,
+    CommissionsReport(CommissionReport),
+    Position(Position),
     PositionEnd,
     AccountSummary(AccountSummary),
     AccountSummaryEnd(i32),
@@ -58,8 +76,8 @@ pub enum IncomingMessagesEnum {
     PositionMultiEnd,
     AccountUpdateMulti,
     AccountUpdateMultiEnd,
-    SecurityDefinitionOptionParameter,
-    SecurityDefinitionOptionParameterEnd,
+    SecurityDefinitionOptionParameter(i32, OptionChain),
+    SecurityDefinitionOptionParameterEnd(i32),
     SoftDollarTier,
     FamilyCodes,
     SymbolSamples,
@@ -71,21 +89,28 @@ pub enum IncomingMessagesEnum {
     NewsProviders,
     HistoricalNews,
     HistoricalNewsEnd,
-    HeadTimestamp,
-    HistogramData,
-    HistoricalDataUpdate,
+    HeadTimestamp(i32, String),
+    HistogramData(i32, Vec<HistogramEntry>),
+    HistoricalDataUpdate(i32, Bar),
     RerouteMktDataReq,
     RerouteMktDepthReq,
     MarketRule,
-    PnL(i32, f64, f64, f64),
-    PnLsingle,
-    HistoricalTick,
-    HistoricalTickBidAsk,
-    HistoricalTickLast,
+    PnL(PnL),
+    PnLSingle(PnLSingle),
+    // req_id, ticks, done
+    HistoricalTick(i32, Vec<HistoricalTick>, bool),
+    HistoricalTickBidAsk(i32, Vec<TickBidAsk>, bool),
+    HistoricalTickLast(i32, Vec<TickLast>, bool),
     TickByTick,
     TickByTickLast((i32, TickLast)),
-    OrderBound,
-    CompletedOrder,
+    TickByTickBidAsk((i32, TickBidAsk)),
+    TickByTickMidPoint((i32, TickMidPoint)),
+    OrderBound(OrderBound),
+    CompletedOrder(OrderDataItem),
     CompletedOrdersEnd,
+    Disconnected,
+    Reconnected,
+    // (previous, new) server version after an automatic reconnect
+    ServerVersionChanged(i32, i32),
     Stop,
 }
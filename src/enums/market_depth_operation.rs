@@ -0,0 +1,18 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarketDepthOperation {
+    Insert = 0,
+    Update = 1,
+    Delete = 2,
+    Unknown,
+}
+
+impl From<i32> for MarketDepthOperation {
+    fn from(val: i32) -> Self {
+        match val {
+            0 => MarketDepthOperation::Insert,
+            1 => MarketDepthOperation::Update,
+            2 => MarketDepthOperation::Delete,
+            _ => MarketDepthOperation::Unknown
+        }
+    }
+}
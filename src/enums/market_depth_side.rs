@@ -0,0 +1,16 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarketDepthSide {
+    Ask = 0,
+    Bid = 1,
+    Unknown,
+}
+
+impl From<i32> for MarketDepthSide {
+    fn from(val: i32) -> Self {
+        match val {
+            0 => MarketDepthSide::Ask,
+            1 => MarketDepthSide::Bid,
+            _ => MarketDepthSide::Unknown
+        }
+    }
+}
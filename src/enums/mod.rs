@@ -5,3 +5,13 @@ pub mod origin;
 pub mod trigger_method;
 pub mod order_condition_type;
 pub mod position_type;
+pub mod tick_type;
+pub mod market_depth_operation;
+pub mod market_depth_side;
+pub mod account_summary_tag;
+pub mod volatility_basis;
+pub mod exercise_action;
+pub mod subscription;
+pub mod order_lifecycle_state;
+pub mod oca_type;
+pub mod adjusted_stop;
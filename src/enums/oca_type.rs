@@ -0,0 +1,10 @@
+// What happens to the other orders of an OCA group when one of them fills. With the reduce
+// types the remaining orders shrink by the filled quantity, so a group can scale out of a
+// position instead of cancelling everything on the first fill. Block protects against
+// overfills by holding the other orders while one is being filled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OcaType {
+    CancelWithBlock = 1,
+    ReduceWithBlock = 2,
+    ReduceNonBlock = 3,
+}
@@ -0,0 +1,37 @@
+// Where an order managed by OrderManager stands. The IB order statuses are folded into fewer
+// states: ApiPending counts as PendingSubmit, PreSubmitted as Submitted and ApiCancelled as
+// Cancelled, and a working order with fills is PartiallyFilled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderLifecycleState {
+    PendingSubmit,
+    Submitted,
+    PartiallyFilled,
+    PendingCancel,
+    Filled,
+    Cancelled,
+    Inactive,
+    // refused by TWS before it was accepted, the reason comes with the Error event
+    Rejected,
+}
+
+impl OrderLifecycleState {
+    pub fn from_status(status: &str, filled: f64) -> Option<OrderLifecycleState> {
+        let state = match status {
+            "ApiPending" | "PendingSubmit" => OrderLifecycleState::PendingSubmit,
+            "PreSubmitted" | "Submitted" if filled > 0.0 => OrderLifecycleState::PartiallyFilled,
+            "PreSubmitted" | "Submitted" => OrderLifecycleState::Submitted,
+            "PendingCancel" => OrderLifecycleState::PendingCancel,
+            "ApiCancelled" | "Cancelled" => OrderLifecycleState::Cancelled,
+            "Filled" => OrderLifecycleState::Filled,
+            "Inactive" => OrderLifecycleState::Inactive,
+            _ => return None
+        };
+
+        Some(state)
+    }
+
+    // No further updates are expected once an order reached a final state.
+    pub fn is_final(&self) -> bool {
+        matches!(self, OrderLifecycleState::Filled | OrderLifecycleState::Cancelled | OrderLifecycleState::Inactive | OrderLifecycleState::Rejected)
+    }
+}
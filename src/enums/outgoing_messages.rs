@@ -1,3 +1,4 @@
+#[derive(Clone, Copy)]
 pub enum OutgoingMessages {
     RequestMarketData = 1,
     CancelMarketData = 2,
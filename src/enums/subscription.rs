@@ -0,0 +1,13 @@
+// The kind of a stream that is active for a req_id, which decides the cancel message to send.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Subscription {
+    MarketData,
+    ScannerSubscription,
+    AccountSummary,
+    HistoricalData,
+    RealTimeBars,
+    TickByTick,
+    PnL,
+    PnLSingle,
+    MarketDepth { is_smart_depth: bool },
+}
@@ -0,0 +1,383 @@
+use core::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TickType {
+    BidSize = 0,
+    Bid = 1,
+    Ask = 2,
+    AskSize = 3,
+    Last = 4,
+    LastSize = 5,
+    High = 6,
+    Low = 7,
+    Volume = 8,
+    Close = 9,
+    BidOptionComputation = 10,
+    AskOptionComputation = 11,
+    LastOptionComputation = 12,
+    ModelOptionComputation = 13,
+    Open = 14,
+    Low13Week = 15,
+    High13Week = 16,
+    Low26Week = 17,
+    High26Week = 18,
+    Low52Week = 19,
+    High52Week = 20,
+    AvgVolume = 21,
+    OpenInterest = 22,
+    OptionHistoricalVol = 23,
+    OptionImpliedVol = 24,
+    OptionBidExch = 25,
+    OptionAskExch = 26,
+    OptionCallOpenInterest = 27,
+    OptionPutOpenInterest = 28,
+    OptionCallVolume = 29,
+    OptionPutVolume = 30,
+    IndexFuturePremium = 31,
+    BidExch = 32,
+    AskExch = 33,
+    AuctionVolume = 34,
+    AuctionPrice = 35,
+    AuctionImbalance = 36,
+    MarkPrice = 37,
+    BidEfpComputation = 38,
+    AskEfpComputation = 39,
+    LastEfpComputation = 40,
+    OpenEfpComputation = 41,
+    HighEfpComputation = 42,
+    LowEfpComputation = 43,
+    CloseEfpComputation = 44,
+    LastTimestamp = 45,
+    Shortable = 46,
+    FundamentalRatios = 47,
+    RtVolume = 48,
+    Halted = 49,
+    BidYield = 50,
+    AskYield = 51,
+    LastYield = 52,
+    CustOptionComputation = 53,
+    TradeCount = 54,
+    TradeRate = 55,
+    VolumeRate = 56,
+    LastRthTrade = 57,
+    RtHistoricalVol = 58,
+    IbDividends = 59,
+    BondFactorMultiplier = 60,
+    RegulatoryImbalance = 61,
+    NewsTick = 62,
+    ShortTermVolume3Min = 63,
+    ShortTermVolume5Min = 64,
+    ShortTermVolume10Min = 65,
+    DelayedBid = 66,
+    DelayedAsk = 67,
+    DelayedLast = 68,
+    DelayedBidSize = 69,
+    DelayedAskSize = 70,
+    DelayedLastSize = 71,
+    DelayedHigh = 72,
+    DelayedLow = 73,
+    DelayedVolume = 74,
+    DelayedClose = 75,
+    DelayedOpen = 76,
+    RtTrdVolume = 77,
+    CreditmanMarkPrice = 78,
+    CreditmanSlowMarkPrice = 79,
+    DelayedBidOptionComputation = 80,
+    DelayedAskOptionComputation = 81,
+    DelayedLastOptionComputation = 82,
+    DelayedModelOptionComputation = 83,
+    LastExch = 84,
+    LastRegTime = 85,
+    FuturesOpenInterest = 86,
+    AvgOptVolume = 87,
+    DelayedLastTimestamp = 88,
+    ShortableShares = 89,
+    DelayedHalted = 90,
+    Reuters2MutualFunds = 91,
+    EtfNavClose = 92,
+    EtfNavPriorClose = 93,
+    EtfNavBid = 94,
+    EtfNavAsk = 95,
+    EtfNavLast = 96,
+    EtfFrozenNavLast = 97,
+    EtfNavHigh = 98,
+    EtfNavLow = 99,
+    SocialMarketAnalytics = 100,
+    NotSet,
+}
+
+impl From<i32> for TickType {
+    fn from(val: i32) -> Self {
+        match val {
+            0 => TickType::BidSize,
+            1 => TickType::Bid,
+            2 => TickType::Ask,
+            3 => TickType::AskSize,
+            4 => TickType::Last,
+            5 => TickType::LastSize,
+            6 => TickType::High,
+            7 => TickType::Low,
+            8 => TickType::Volume,
+            9 => TickType::Close,
+            10 => TickType::BidOptionComputation,
+            11 => TickType::AskOptionComputation,
+            12 => TickType::LastOptionComputation,
+            13 => TickType::ModelOptionComputation,
+            14 => TickType::Open,
+            15 => TickType::Low13Week,
+            16 => TickType::High13Week,
+            17 => TickType::Low26Week,
+            18 => TickType::High26Week,
+            19 => TickType::Low52Week,
+            20 => TickType::High52Week,
+            21 => TickType::AvgVolume,
+            22 => TickType::OpenInterest,
+            23 => TickType::OptionHistoricalVol,
+            24 => TickType::OptionImpliedVol,
+            25 => TickType::OptionBidExch,
+            26 => TickType::OptionAskExch,
+            27 => TickType::OptionCallOpenInterest,
+            28 => TickType::OptionPutOpenInterest,
+            29 => TickType::OptionCallVolume,
+            30 => TickType::OptionPutVolume,
+            31 => TickType::IndexFuturePremium,
+            32 => TickType::BidExch,
+            33 => TickType::AskExch,
+            34 => TickType::AuctionVolume,
+            35 => TickType::AuctionPrice,
+            36 => TickType::AuctionImbalance,
+            37 => TickType::MarkPrice,
+            38 => TickType::BidEfpComputation,
+            39 => TickType::AskEfpComputation,
+            40 => TickType::LastEfpComputation,
+            41 => TickType::OpenEfpComputation,
+            42 => TickType::HighEfpComputation,
+            43 => TickType::LowEfpComputation,
+            44 => TickType::CloseEfpComputation,
+            45 => TickType::LastTimestamp,
+            46 => TickType::Shortable,
+            47 => TickType::FundamentalRatios,
+            48 => TickType::RtVolume,
+            49 => TickType::Halted,
+            50 => TickType::BidYield,
+            51 => TickType::AskYield,
+            52 => TickType::LastYield,
+            53 => TickType::CustOptionComputation,
+            54 => TickType::TradeCount,
+            55 => TickType::TradeRate,
+            56 => TickType::VolumeRate,
+            57 => TickType::LastRthTrade,
+            58 => TickType::RtHistoricalVol,
+            59 => TickType::IbDividends,
+            60 => TickType::BondFactorMultiplier,
+            61 => TickType::RegulatoryImbalance,
+            62 => TickType::NewsTick,
+            63 => TickType::ShortTermVolume3Min,
+            64 => TickType::ShortTermVolume5Min,
+            65 => TickType::ShortTermVolume10Min,
+            66 => TickType::DelayedBid,
+            67 => TickType::DelayedAsk,
+            68 => TickType::DelayedLast,
+            69 => TickType::DelayedBidSize,
+            70 => TickType::DelayedAskSize,
+            71 => TickType::DelayedLastSize,
+            72 => TickType::DelayedHigh,
+            73 => TickType::DelayedLow,
+            74 => TickType::DelayedVolume,
+            75 => TickType::DelayedClose,
+            76 => TickType::DelayedOpen,
+            77 => TickType::RtTrdVolume,
+            78 => TickType::CreditmanMarkPrice,
+            79 => TickType::CreditmanSlowMarkPrice,
+            80 => TickType::DelayedBidOptionComputation,
+            81 => TickType::DelayedAskOptionComputation,
+            82 => TickType::DelayedLastOptionComputation,
+            83 => TickType::DelayedModelOptionComputation,
+            84 => TickType::LastExch,
+            85 => TickType::LastRegTime,
+            86 => TickType::FuturesOpenInterest,
+            87 => TickType::AvgOptVolume,
+            88 => TickType::DelayedLastTimestamp,
+            89 => TickType::ShortableShares,
+            90 => TickType::DelayedHalted,
+            91 => TickType::Reuters2MutualFunds,
+            92 => TickType::EtfNavClose,
+            93 => TickType::EtfNavPriorClose,
+            94 => TickType::EtfNavBid,
+            95 => TickType::EtfNavAsk,
+            96 => TickType::EtfNavLast,
+            97 => TickType::EtfFrozenNavLast,
+            98 => TickType::EtfNavHigh,
+            99 => TickType::EtfNavLow,
+            100 => TickType::SocialMarketAnalytics,
+            _ => TickType::NotSet
+        }
+    }
+}
+
+impl TickType {
+    // The tick type TWS sends instead of this one when the market data type is delayed,
+    // e.g. Bid -> DelayedBid. Types without a delayed counterpart map to themselves.
+    pub fn delayed(self) -> TickType {
+        match self {
+            TickType::Bid => TickType::DelayedBid,
+            TickType::Ask => TickType::DelayedAsk,
+            TickType::Last => TickType::DelayedLast,
+            TickType::BidSize => TickType::DelayedBidSize,
+            TickType::AskSize => TickType::DelayedAskSize,
+            TickType::LastSize => TickType::DelayedLastSize,
+            TickType::High => TickType::DelayedHigh,
+            TickType::Low => TickType::DelayedLow,
+            TickType::Volume => TickType::DelayedVolume,
+            TickType::Close => TickType::DelayedClose,
+            TickType::Open => TickType::DelayedOpen,
+            TickType::BidOptionComputation => TickType::DelayedBidOptionComputation,
+            TickType::AskOptionComputation => TickType::DelayedAskOptionComputation,
+            TickType::LastOptionComputation => TickType::DelayedLastOptionComputation,
+            TickType::ModelOptionComputation => TickType::DelayedModelOptionComputation,
+            TickType::LastTimestamp => TickType::DelayedLastTimestamp,
+            TickType::Halted => TickType::DelayedHalted,
+            _ => self
+        }
+    }
+
+    // The live tick type a delayed one stands in for, e.g. DelayedBid -> Bid.
+    pub fn live(self) -> TickType {
+        match self {
+            TickType::DelayedBid => TickType::Bid,
+            TickType::DelayedAsk => TickType::Ask,
+            TickType::DelayedLast => TickType::Last,
+            TickType::DelayedBidSize => TickType::BidSize,
+            TickType::DelayedAskSize => TickType::AskSize,
+            TickType::DelayedLastSize => TickType::LastSize,
+            TickType::DelayedHigh => TickType::High,
+            TickType::DelayedLow => TickType::Low,
+            TickType::DelayedVolume => TickType::Volume,
+            TickType::DelayedClose => TickType::Close,
+            TickType::DelayedOpen => TickType::Open,
+            TickType::DelayedBidOptionComputation => TickType::BidOptionComputation,
+            TickType::DelayedAskOptionComputation => TickType::AskOptionComputation,
+            TickType::DelayedLastOptionComputation => TickType::LastOptionComputation,
+            TickType::DelayedModelOptionComputation => TickType::ModelOptionComputation,
+            TickType::DelayedLastTimestamp => TickType::LastTimestamp,
+            TickType::DelayedHalted => TickType::Halted,
+            _ => self
+        }
+    }
+
+    pub fn is_delayed(self) -> bool {
+        self.live() != self
+    }
+}
+
+impl fmt::Display for TickType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TickType::BidSize => "bidSize",
+            TickType::Bid => "bidPrice",
+            TickType::Ask => "askPrice",
+            TickType::AskSize => "askSize",
+            TickType::Last => "lastPrice",
+            TickType::LastSize => "lastSize",
+            TickType::High => "high",
+            TickType::Low => "low",
+            TickType::Volume => "volume",
+            TickType::Close => "close",
+            TickType::BidOptionComputation => "bidOptComp",
+            TickType::AskOptionComputation => "askOptComp",
+            TickType::LastOptionComputation => "lastOptComp",
+            TickType::ModelOptionComputation => "modelOptComp",
+            TickType::Open => "open",
+            TickType::Low13Week => "13WeekLow",
+            TickType::High13Week => "13WeekHigh",
+            TickType::Low26Week => "26WeekLow",
+            TickType::High26Week => "26WeekHigh",
+            TickType::Low52Week => "52WeekLow",
+            TickType::High52Week => "52WeekHigh",
+            TickType::AvgVolume => "AvgVolume",
+            TickType::OpenInterest => "OpenInterest",
+            TickType::OptionHistoricalVol => "OptionHistoricalVolatility",
+            TickType::OptionImpliedVol => "OptionImpliedVolatility",
+            TickType::OptionBidExch => "OptionBidExchStr",
+            TickType::OptionAskExch => "OptionAskExchStr",
+            TickType::OptionCallOpenInterest => "OptionCallOpenInterest",
+            TickType::OptionPutOpenInterest => "OptionPutOpenInterest",
+            TickType::OptionCallVolume => "OptionCallVolume",
+            TickType::OptionPutVolume => "OptionPutVolume",
+            TickType::IndexFuturePremium => "IndexFuturePremium",
+            TickType::BidExch => "bidExch",
+            TickType::AskExch => "askExch",
+            TickType::AuctionVolume => "auctionVolume",
+            TickType::AuctionPrice => "auctionPrice",
+            TickType::AuctionImbalance => "auctionImbalance",
+            TickType::MarkPrice => "markPrice",
+            TickType::BidEfpComputation => "bidEFP",
+            TickType::AskEfpComputation => "askEFP",
+            TickType::LastEfpComputation => "lastEFP",
+            TickType::OpenEfpComputation => "openEFP",
+            TickType::HighEfpComputation => "highEFP",
+            TickType::LowEfpComputation => "lowEFP",
+            TickType::CloseEfpComputation => "closeEFP",
+            TickType::LastTimestamp => "lastTimestamp",
+            TickType::Shortable => "shortable",
+            TickType::FundamentalRatios => "fundamentals",
+            TickType::RtVolume => "RTVolume",
+            TickType::Halted => "halted",
+            TickType::BidYield => "bidYield",
+            TickType::AskYield => "askYield",
+            TickType::LastYield => "lastYield",
+            TickType::CustOptionComputation => "custOptComp",
+            TickType::TradeCount => "trades",
+            TickType::TradeRate => "trades/min",
+            TickType::VolumeRate => "volume/min",
+            TickType::LastRthTrade => "lastRTHTrade",
+            TickType::RtHistoricalVol => "RTHistoricalVol",
+            TickType::IbDividends => "IBDividends",
+            TickType::BondFactorMultiplier => "bondFactorMultiplier",
+            TickType::RegulatoryImbalance => "regulatoryImbalance",
+            TickType::NewsTick => "newsTick",
+            TickType::ShortTermVolume3Min => "shortTermVolume3Min",
+            TickType::ShortTermVolume5Min => "shortTermVolume5Min",
+            TickType::ShortTermVolume10Min => "shortTermVolume10Min",
+            TickType::DelayedBid => "delayedBid",
+            TickType::DelayedAsk => "delayedAsk",
+            TickType::DelayedLast => "delayedLast",
+            TickType::DelayedBidSize => "delayedBidSize",
+            TickType::DelayedAskSize => "delayedAskSize",
+            TickType::DelayedLastSize => "delayedLastSize",
+            TickType::DelayedHigh => "delayedHigh",
+            TickType::DelayedLow => "delayedLow",
+            TickType::DelayedVolume => "delayedVolume",
+            TickType::DelayedClose => "delayedClose",
+            TickType::DelayedOpen => "delayedOpen",
+            TickType::RtTrdVolume => "rtTrdVolume",
+            TickType::CreditmanMarkPrice => "creditmanMarkPrice",
+            TickType::CreditmanSlowMarkPrice => "creditmanSlowMarkPrice",
+            TickType::DelayedBidOptionComputation => "delayedBidOptComp",
+            TickType::DelayedAskOptionComputation => "delayedAskOptComp",
+            TickType::DelayedLastOptionComputation => "delayedLastOptComp",
+            TickType::DelayedModelOptionComputation => "delayedModelOptComp",
+            TickType::LastExch => "lastExchange",
+            TickType::LastRegTime => "lastRegTime",
+            TickType::FuturesOpenInterest => "futuresOpenInterest",
+            TickType::AvgOptVolume => "avgOptVolume",
+            TickType::DelayedLastTimestamp => "delayedLastTimestamp",
+            TickType::ShortableShares => "shortableShares",
+            TickType::DelayedHalted => "delayedHalted",
+            TickType::Reuters2MutualFunds => "reuters2MutualFunds",
+            TickType::EtfNavClose => "etfNavClose",
+            TickType::EtfNavPriorClose => "etfNavPriorClose",
+            TickType::EtfNavBid => "etfNavBid",
+            TickType::EtfNavAsk => "etfNavAsk",
+            TickType::EtfNavLast => "etfNavLast",
+            TickType::EtfFrozenNavLast => "etfFrozenNavLast",
+            TickType::EtfNavHigh => "etfNavHigh",
+            TickType::EtfNavLow => "etfNavLow",
+            TickType::SocialMarketAnalytics => "socialMarketAnalytics",
+            TickType::NotSet => "notSet"
+        };
+
+        write!(f, "{}", name)
+    }
+}
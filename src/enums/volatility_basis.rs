@@ -0,0 +1,6 @@
+// Tells how the implied volatility of an option computation was derived.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VolatilityBasis {
+    ReturnBased = 0,
+    PriceBased = 1,
+}
@@ -7,16 +7,16 @@ pub static UPDATE_TWS: (i32, &str) = (503, "The TWS is out of date and must be u
 pub static NOT_CONNECTED: (i32, &str) = (504, "Not connected");
 //pub static UNKNOWN_ID: (i32, &str) = (505, "Fatal Error: Unknown message id.");
 pub static FAIL_SEND_REQMKT: (i32, &str) = (510, "Request Market Data Sending Error - ");
-//pub static FAIL_SEND_CANMKT: (i32, &str) = (511, "Cancel Market Data Sending Error - ");
+pub static FAIL_SEND_CANMKT: (i32, &str) = (511, "Cancel Market Data Sending Error - ");
 pub static FAIL_SEND_ORDER: (i32, &str) = (512, "Order Sending Error - ");
-//pub static FAIL_SEND_ACCT: (i32, &str) = (513, "Account Update Request Sending Error -");
-//pub static FAIL_SEND_EXEC: (i32, &str) = (514, "Request For Executions Sending Error -");
+pub static FAIL_SEND_ACCT: (i32, &str) = (513, "Account Update Request Sending Error - ");
+pub static FAIL_SEND_EXEC: (i32, &str) = (514, "Request For Executions Sending Error - ");
 //pub static FAIL_SEND_CORDER: (i32, &str) = (515, "Cancel Order Sending Error -");
-//pub static FAIL_SEND_OORDER: (i32, &str) = (516, "Request Open Order Sending Error -");
+pub static FAIL_SEND_OORDER: (i32, &str) = (516, "Request Open Order Sending Error -");
 //pub static UNKNOWN_CONTRACT: (i32, &str) = (517, "Unknown contract. Verify the contract details supplied.");
 pub static FAIL_SEND_REQCONTRACT: (i32, &str) = (518, "Request Contract Data Sending Error - ");
-//pub static FAIL_SEND_REQMKTDEPTH: (i32, &str) = (519, "Request Market Depth Sending Error - ");
-//pub static FAIL_SEND_CANMKTDEPTH: (i32, &str) = (520, "Cancel Market Depth Sending Error - ");
+pub static FAIL_SEND_REQMKTDEPTH: (i32, &str) = (519, "Request Market Depth Sending Error - ");
+pub static FAIL_SEND_CANMKTDEPTH: (i32, &str) = (520, "Cancel Market Depth Sending Error - ");
 //pub static FAIL_SEND_SERVER_LOG_LEVEL: (i32, &str) = (521, "Set Server Log Level Sending Error - ");
 //pub static FAIL_SEND_FA_REQUEST: (i32, &str) = (522, "FA Information Request Sending Error - ");
 //pub static FAIL_SEND_FA_REPLACE: (i32, &str) = (523, "FA Information Replace Sending Error - ");
@@ -24,22 +24,22 @@ pub static FAIL_SEND_REQCONTRACT: (i32, &str) = (518, "Request Contract Data Sen
 pub static FAIL_SEND_CANSCANNER: (i32, &str) = (525, "Cancel Scanner Subscription Sending Error - ");
 //pub static FAIL_SEND_REQSCANNERPARAMETERS: (i32, &str) = (526, "Request Scanner Parameter Sending Error - ");
 pub static FAIL_SEND_REQHISTDATA: (i32, &str) = (527, "Request Historical Data Sending Error - ");
-//pub static FAIL_SEND_CANHISTDATA: (i32, &str) = (528, "Request Historical Data Sending Error - ");
+pub static FAIL_SEND_CANHISTDATA: (i32, &str) = (528, "Cancel Historical Data Sending Error - ");
 pub static FAIL_SEND_REQRTBARS: (i32, &str) = (529, "Request Real-time Bar Data Sending Error - ");
-//pub static FAIL_SEND_CANRTBARS: (i32, &str) = (530, "Cancel Real-time Bar Data Sending Error - ");
+pub static FAIL_SEND_CANRTBARS: (i32, &str) = (530, "Cancel Real-time Bar Data Sending Error - ");
 //pub static FAIL_SEND_REQCURRTIME: (i32, &str) = (531, "Request Current Time Sending Error - ");
 //pub static FAIL_SEND_REQFUNDDATA: (i32, &str) = (532, "Request Fundamental Data Sending Error - ");
 //pub static FAIL_SEND_CANFUNDDATA: (i32, &str) = (533, "Cancel Fundamental Data Sending Error - ");
-//pub static FAIL_SEND_REQCALCIMPLIEDVOLAT: (i32, &str) = (534, "Request Calculate Implied Volatility Sending Error - ");
-//pub static FAIL_SEND_REQCALCOPTIONPRICE: (i32, &str) = (535, "Request Calculate Option Price Sending Error - ");
-//pub static FAIL_SEND_CANCALCIMPLIEDVOLAT: (i32, &str) = (536, "Cancel Calculate Implied Volatility Sending Error - ");
-//pub static FAIL_SEND_CANCALCOPTIONPRICE: (i32, &str) = (537, "Cancel Calculate Option Price Sending Error - ");
+pub static FAIL_SEND_REQCALCIMPLIEDVOLAT: (i32, &str) = (534, "Request Calculate Implied Volatility Sending Error - ");
+pub static FAIL_SEND_REQCALCOPTIONPRICE: (i32, &str) = (535, "Request Calculate Option Price Sending Error - ");
+pub static FAIL_SEND_CANCALCIMPLIEDVOLAT: (i32, &str) = (536, "Cancel Calculate Implied Volatility Sending Error - ");
+pub static FAIL_SEND_CANCALCOPTIONPRICE: (i32, &str) = (537, "Cancel Calculate Option Price Sending Error - ");
 pub static FAIL_SEND_REQGLOBALCANCEL: (i32, &str) = (538, "Request Global Cancel Sending Error - ");
 //pub static FAIL_SEND_REQMARKETDATATYPE: (i32, &str) = (539, "Request Market Data Type Sending Error - ");
-//pub static FAIL_SEND_REQPOSITIONS: (i32, &str) = (540, "Request Positions Sending Error - ");
-//pub static FAIL_SEND_CANPOSITIONS: (i32, &str) = (541, "Cancel Positions Sending Error - ");
-//pub static FAIL_SEND_REQACCOUNTDATA: (i32, &str) = (542, "Request Account Data Sending Error - ");
-//pub static FAIL_SEND_CANACCOUNTDATA: (i32, &str) = (543, "Cancel Account Data Sending Error - ");
+pub static FAIL_SEND_REQPOSITIONS: (i32, &str) = (540, "Request Positions Sending Error - ");
+pub static FAIL_SEND_CANPOSITIONS: (i32, &str) = (541, "Cancel Positions Sending Error - ");
+pub static FAIL_SEND_REQACCOUNTDATA: (i32, &str) = (542, "Request Account Data Sending Error - ");
+pub static FAIL_SEND_CANACCOUNTDATA: (i32, &str) = (543, "Cancel Account Data Sending Error - ");
 //pub static FAIL_SEND_VERIFYREQUEST: (i32, &str) = (544, "Verify Request Sending Error - ");
 //pub static FAIL_SEND_VERIFYMESSAGE: (i32, &str) = (545, "Verify Message Sending Error - ");
 //pub static FAIL_SEND_QUERYDISPLAYGROUPS: (i32, &str) = (546, "Query Display Groups Sending Error - ");
@@ -55,7 +55,7 @@ pub static FAIL_SEND_REQGLOBALCANCEL: (i32, &str) = (538, "Request Global Cancel
 //pub static FAIL_SEND_CANPOSITIONSMULTI: (i32, &str) = (554, "Cancel Positions Multi Sending Error - ");
 //pub static FAIL_SEND_REQACCOUNTUPDATESMULTI: (i32, &str) = (555, "Request Account Updates Multi Sending Error - ");
 //pub static FAIL_SEND_CANACCOUNTUPDATESMULTI: (i32, &str) = (556, "Cancel Account Updates Multi Sending Error - ");
-//pub static FAIL_SEND_REQSECDEFOPTPARAMS: (i32, &str) = (557, "Request Security Definition Option Parameters Sending Error - ");
+pub static FAIL_SEND_REQSECDEFOPTPARAMS: (i32, &str) = (557, "Request Security Definition Option Parameters Sending Error - ");
 //pub static FAIL_SEND_REQSOFTDOLLARTIERS: (i32, &str) = (558, "Request Soft Dollar Tiers Sending Error - ");
 //pub static FAIL_SEND_REQFAMILYCODES: (i32, &str) = (559, "Request Family Codes Sending Error - ");
 //pub static FAIL_SEND_REQMATCHINGSYMBOLS: (i32, &str) = (560, "Request Matching Symbols Sending Error - ");
@@ -64,17 +64,17 @@ pub static FAIL_SEND_REQGLOBALCANCEL: (i32, &str) = (538, "Request Global Cancel
 //pub static FAIL_SEND_REQNEWSPROVIDERS: (i32, &str) = (563, "Request News Providers Sending Error - ");
 //pub static FAIL_SEND_REQNEWSARTICLE: (i32, &str) = (564, "Request News Article Sending Error - ");
 //pub static FAIL_SEND_REQHISTORICALNEWS: (i32, &str) = (565, "Request Historical News Sending Error - ");
-//pub static FAIL_SEND_REQHEADTIMESTAMP: (i32, &str) = (566, "Request Head Time Stamp Sending Error - ");
-//pub static FAIL_SEND_REQHISTOGRAMDATA: (i32, &str) = (567, "Request Histogram Data Sending Error - ");
-//pub static FAIL_SEND_CANCELHISTOGRAMDATA: (i32, &str) = (568, "Cancel Request Histogram Data Sending Error - ");
-//pub static FAIL_SEND_CANCELHEADTIMESTAMP: (i32, &str) = (569, "Cancel Head Time Stamp Sending Error - ");
+pub static FAIL_SEND_REQHEADTIMESTAMP: (i32, &str) = (566, "Request Head Time Stamp Sending Error - ");
+pub static FAIL_SEND_REQHISTOGRAMDATA: (i32, &str) = (567, "Request Histogram Data Sending Error - ");
+pub static FAIL_SEND_CANCELHISTOGRAMDATA: (i32, &str) = (568, "Cancel Request Histogram Data Sending Error - ");
+pub static FAIL_SEND_CANCELHEADTIMESTAMP: (i32, &str) = (569, "Cancel Head Time Stamp Sending Error - ");
 //pub static FAIL_SEND_REQMARKETRULE: (i32, &str) = (570, "Request Market Rule Sending Error - ");
-//pub static FAIL_SEND_REQPNL: (i32, &str) = (571, "Request PnL Sending Error - ");
-//pub static FAIL_SEND_CANCELPNL: (i32, &str) = (572, "Cancel PnL Sending Error - ");
-//pub static FAIL_SEND_REQPNLSINGLE: (i32, &str) = (573, "Request PnL Single Error - ");
-//pub static FAIL_SEND_CANCELPNLSINGLE: (i32, &str) = (574, "Cancel PnL Single Sending Error - ");
-//pub static FAIL_SEND_REQHISTORICALTICKS: (i32, &str) = (575, "Request Historical Ticks Error - ");
+pub static FAIL_SEND_REQPNL: (i32, &str) = (571, "Request PnL Sending Error - ");
+pub static FAIL_SEND_CANCELPNL: (i32, &str) = (572, "Cancel PnL Sending Error - ");
+pub static FAIL_SEND_REQPNLSINGLE: (i32, &str) = (573, "Request PnL Single Error - ");
+pub static FAIL_SEND_CANCELPNLSINGLE: (i32, &str) = (574, "Cancel PnL Single Sending Error - ");
+pub static FAIL_SEND_REQHISTORICALTICKS: (i32, &str) = (575, "Request Historical Ticks Error - ");
 pub static FAIL_SEND_REQTICKBYTICKDATA: (i32, &str) = (576, "Request Tick-By-Tick Data Sending Error - ");
 pub static FAIL_SEND_CANCELTICKBYTICKDATA: (i32, &str) = (577, "Cancel Tick-By-Tick Data Sending Error - ");
-//pub static FAIL_SEND_REQCOMPLETEDORDERS: (i32, &str) = (578, "Request Completed Orders Sending Error - ");
-//pub static FAIL_GENERIC: (i32, &str) = (-1, "Specific error message needs to be given for these requests! ");
+pub static FAIL_SEND_REQCOMPLETEDORDERS: (i32, &str) = (578, "Request Completed Orders Sending Error - ");
+pub static FAIL_GENERIC: (i32, &str) = (-1, "Specific error message needs to be given for these requests! ");
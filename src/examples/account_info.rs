@@ -1,6 +1,8 @@
 use std::error::Error;
 use ib_rust_api::IbClient;
 use ib_rust_api::enums::incoming_message_enum::IncomingMessagesEnum;
+use ib_rust_api::enums::account_summary_tag::AccountSummaryTag;
+use ib_rust_api::models::account_summary_snapshot::AccountSummarySnapshot;
 
 fn main() -> Result<(), Box<dyn Error>> {
     println!("Running live quotes example");
@@ -8,7 +10,8 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut ib_client = IbClient::new("127.0.0.1".to_string(), 7497, 3);
     ib_client.connect()?;
 
-    ib_client.req_account_summary(6);
+    ib_client.req_account_summary(6, "All", &AccountSummaryTag::all());
+    let mut snapshot = AccountSummarySnapshot::new(6);
 
     loop {
         let event = ib_client.event_receiver.recv().unwrap();
@@ -21,9 +24,15 @@ fn main() -> Result<(), Box<dyn Error>> {
             },
             IncomingMessagesEnum::AccountSummary(account_summary) => {
                 println!("Account Summary: Account, {}, tag, {}, value, {}, currency, {}", account_summary.account, account_summary.tag, account_summary.value, account_summary.currency);
+                snapshot.update(&account_summary);
             },
-            IncomingMessagesEnum::AccountSummaryEnd(_) => {
-                println!("Account summary end");
+            IncomingMessagesEnum::AccountSummaryEnd(req_id) => {
+                snapshot.end(req_id);
+                for account in snapshot.accounts() {
+                    if let Some(net_liquidation) = snapshot.value(account, "NetLiquidation") {
+                        println!("Account summary end: {} net liquidation {} {}", account, net_liquidation.value, net_liquidation.currency);
+                    }
+                }
             }
             _ => {
                 println!("Event not handled");
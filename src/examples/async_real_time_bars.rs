@@ -0,0 +1,34 @@
+use std::error::Error;
+use ib_rust_api::async_client::AsyncIbClient;
+use ib_rust_api::enums::incoming_message_enum::IncomingMessagesEnum;
+use ib_rust_api::models::contract::Contract;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    println!("Running async real time bars example");
+
+    let (mut ib_client, mut events) = AsyncIbClient::connect("127.0.0.1", 7497, 2).await?;
+
+    let mut contract = Contract::new();
+    contract.currency = "USD".to_string();
+    contract.exchange = "SMART".to_string();
+    contract.sec_type = "STK".to_string();
+    contract.symbol = "AAPL".to_string();
+    ib_client.req_real_time_bars(12, contract, 5, "TRADES", false, vec![]).await?;
+
+    while let Some(event) = events.recv().await {
+        match event {
+            IncomingMessagesEnum::Error(id, code, msg) => {
+                println!("ERR: id: {}, code: {}, msg: {}", id, code, msg);
+            },
+            IncomingMessagesEnum::RealTimeBars(req_id, bar) => {
+                println!("req_id: {}, time str: {}, Volume {}, Close {}", req_id, bar.time_str, bar.volume, bar.close)
+            },
+            _ => {
+                println!("Event not handled");
+            }
+        }
+    }
+
+    Ok(())
+}
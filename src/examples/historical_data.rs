@@ -34,7 +34,7 @@ fn main() -> Result<(), Box<dyn Error>> {
             IncomingMessagesEnum::Error(id, code, msg) => {
                 println!("ERR: id: {}, code: {}, msg: {}", id, code, msg);
             },
-            IncomingMessagesEnum::HistoricalData(bar) => {
+            IncomingMessagesEnum::HistoricalData(_req_id, bar) => {
                 println!("time int: {}, time str: {}, Volume {}, Close {}", bar.time_int, bar.time_str, bar.volume, bar.close)
             },
             IncomingMessagesEnum::HistoricalDataEnd(req_id, start_date_time, end_date_time) => {
@@ -16,8 +16,8 @@ fn main() -> Result<(), Box<dyn Error>> {
             IncomingMessagesEnum::Error(id, code, msg) => {
                 println!("ERR: id: {}, code: {}, msg: {}", id, code, msg);
             },
-            IncomingMessagesEnum::PnL(req_id, daily, unrealized, realized) => {
-                println!("Req Id: {}, daily: {}, realized {}, unrealized {}", req_id, daily, realized, unrealized);
+            IncomingMessagesEnum::PnL(pnl) => {
+                println!("Req Id: {}, daily: {}, realized {}, unrealized {}", pnl.req_id, pnl.daily_pnl, pnl.realized_pnl, pnl.unrealized_pnl);
             },
             _ => {
                 println!("Event not handled");
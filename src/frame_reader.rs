@@ -0,0 +1,179 @@
+use std::io::{self, Read, ErrorKind};
+use std::convert::TryInto;
+use crate::constants::helper_constants;
+
+const READ_CHUNK_SIZE: usize = 4096;
+const LENGTH_PREFIX_SIZE: usize = 4;
+
+// Splits a byte stream into the length-prefixed messages sent by TWS. Bytes that do not make up
+// a whole message yet are kept until the next read, so a frame may arrive in any number of pieces.
+pub struct FrameReader<R: Read> {
+    reader: R,
+    buffer: Vec<u8>,
+}
+
+impl<R: Read> FrameReader<R> {
+    pub fn new(reader: R) -> Self {
+        FrameReader {
+            reader,
+            buffer: Vec::new(),
+        }
+    }
+
+    // Returns the payload of the next message, or None when the peer closed the connection
+    // between two messages.
+    pub fn read_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            if let Some(frame) = self.take_frame()? {
+                return Ok(Some(frame));
+            }
+
+            let mut chunk = [0_u8; READ_CHUNK_SIZE];
+            let bytes_read = match self.reader.read(&mut chunk) {
+                Ok(bytes_read) => bytes_read,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+
+            if bytes_read == 0 {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+
+                return Err(io::Error::new(ErrorKind::UnexpectedEof, format!("Connection closed with {} bytes of an unfinished message", self.buffer.len())));
+            }
+
+            self.buffer.extend_from_slice(&chunk[..bytes_read]);
+        }
+    }
+
+    fn take_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        if self.buffer.len() < LENGTH_PREFIX_SIZE {
+            return Ok(None);
+        }
+
+        let msg_size = i32::from_be_bytes(self.buffer[..LENGTH_PREFIX_SIZE].try_into().unwrap());
+
+        if !(0..=helper_constants::MAX_MSG_SIZE).contains(&msg_size) {
+            return Err(io::Error::new(ErrorKind::InvalidData, format!("Bad TCP Message Length {}", msg_size)));
+        }
+
+        let frame_end = LENGTH_PREFIX_SIZE + msg_size as usize;
+        if self.buffer.len() < frame_end {
+            return Ok(None);
+        }
+
+        let frame = self.buffer[LENGTH_PREFIX_SIZE..frame_end].to_vec();
+        self.buffer.drain(..frame_end);
+
+        Ok(Some(frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hands out the underlying bytes in pieces of the given sizes, cycling through them.
+    struct ChunkedReader {
+        data: Vec<u8>,
+        position: usize,
+        chunk_sizes: Vec<usize>,
+        reads: usize,
+    }
+
+    impl Read for ChunkedReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let chunk_size = self.chunk_sizes[self.reads % self.chunk_sizes.len()];
+            self.reads += 1;
+
+            let end = std::cmp::min(self.data.len(), self.position + std::cmp::min(chunk_size, buf.len()));
+            let bytes_read = end - self.position;
+            buf[..bytes_read].copy_from_slice(&self.data[self.position..end]);
+            self.position = end;
+
+            Ok(bytes_read)
+        }
+    }
+
+    fn frame(payload: &[u8]) -> Vec<u8> {
+        let mut bytes = (payload.len() as i32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    fn payloads() -> Vec<Vec<u8>> {
+        vec![
+            b"9\x001\x00123\x00".to_vec(),
+            vec![],
+            b"4\x002\x00-1\x002104\x00Market data farm connection is OK:usfarm\x00".to_vec(),
+            vec![b'x'; 5000],
+        ]
+    }
+
+    fn stream(payloads: &[Vec<u8>]) -> Vec<u8> {
+        payloads.iter().flat_map(|payload| frame(payload)).collect()
+    }
+
+    fn read_all(data: Vec<u8>, chunk_sizes: Vec<usize>) -> io::Result<Vec<Vec<u8>>> {
+        let mut frame_reader = FrameReader::new(ChunkedReader { data, position: 0, chunk_sizes, reads: 0 });
+        let mut frames = vec![];
+
+        while let Some(frame) = frame_reader.read_frame()? {
+            frames.push(frame);
+        }
+
+        Ok(frames)
+    }
+
+    #[test]
+    fn reads_frames_split_at_every_point() {
+        let payloads = payloads();
+        let data = stream(&payloads);
+
+        for split in 1..data.len() {
+            let frames = read_all(data.clone(), vec![split, data.len()]).unwrap();
+            assert_eq!(frames, payloads, "split at {}", split);
+        }
+    }
+
+    #[test]
+    fn reads_frames_from_uneven_chunks() {
+        let payloads = payloads();
+
+        for chunk_sizes in vec![vec![1], vec![3, 1, 7], vec![2, 4096, 5], vec![READ_CHUNK_SIZE]] {
+            let frames = read_all(stream(&payloads), chunk_sizes.clone()).unwrap();
+            assert_eq!(frames, payloads, "chunk sizes {:?}", chunk_sizes);
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_frames() {
+        let mut data = (helper_constants::MAX_MSG_SIZE + 1).to_be_bytes().to_vec();
+        data.extend_from_slice(b"abc");
+
+        let err = read_all(data, vec![2]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn rejects_negative_frame_length() {
+        let err = read_all((-1_i32).to_be_bytes().to_vec(), vec![4]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn closed_connection_between_frames_is_a_clean_end() {
+        let frames = read_all(vec![], vec![1]).unwrap();
+        assert!(frames.is_empty());
+    }
+
+    #[test]
+    fn closed_connection_inside_a_frame_is_an_error() {
+        let mut data = stream(&payloads());
+        data.truncate(data.len() - 1);
+
+        let err = read_all(data, vec![64]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
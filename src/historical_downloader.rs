@@ -0,0 +1,455 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::error::Error;
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, TimeZone, Utc};
+use crate::IbClient;
+use crate::enums::incoming_message_enum::IncomingMessagesEnum;
+use crate::models::bar::Bar;
+use crate::models::contract::Contract;
+
+// IB pacing rules for historical data requests
+const MAX_REQUESTS_PER_WINDOW: usize = 60;
+const REQUEST_WINDOW: Duration = Duration::from_secs(10 * 60);
+const MAX_REQUESTS_PER_BURST: usize = 5;
+const BURST_WINDOW: Duration = Duration::from_secs(2);
+const IDENTICAL_REQUEST_INTERVAL: Duration = Duration::from_secs(15);
+
+const PACING_VIOLATION: i32 = 162;
+const PACING_VIOLATION_BACKOFF: Duration = Duration::from_secs(15);
+const MAX_PACING_RETRIES: u32 = 5;
+
+// Largest duration IB accepts in one request for each bar size, with its length in seconds.
+fn chunk_duration(bar_size: &str) -> Option<(&'static str, i64)> {
+    let chunk = match bar_size {
+        "1 secs" => ("1800 S", 1800),
+        "5 secs" => ("3600 S", 3600),
+        "10 secs" | "15 secs" => ("14400 S", 14400),
+        "30 secs" => ("28800 S", 28800),
+        "1 min" => ("1 D", 86400),
+        "2 mins" => ("2 D", 2 * 86400),
+        "3 mins" | "5 mins" | "10 mins" | "15 mins" | "20 mins" | "30 mins" => ("1 W", 7 * 86400),
+        "1 hour" | "2 hours" | "3 hours" | "4 hours" | "8 hours" => ("1 M", 30 * 86400),
+        "1 day" => ("1 Y", 365 * 86400),
+        _ => return None
+    };
+
+    Some(chunk)
+}
+
+// Keeps the send times of the last ten minutes and tells how long to wait before the next
+// request so none of the pacing rules is broken. IB counts requests per connection, so the
+// pacer is owned by the IbClient, which records every historical data, historical ticks and
+// head timestamp request it sends. Downloaders wait for it before each of their requests,
+// requests made directly on the client are counted but not delayed.
+#[derive(Default)]
+pub(crate) struct Pacer {
+    sent: VecDeque<(Instant, String)>,
+}
+
+impl Pacer {
+    // IB considers two requests identical when they are for the same contract and data with
+    // the same parameters, e.g. end time, duration and bar size.
+    pub(crate) fn request_key(contract: &Contract, what_to_show: &str, parameters: &[&str]) -> String {
+        format!("{} {} {} {} {} {} {} {} {}", contract.con_id, contract.symbol, contract.sec_type, contract.last_trade_date_or_contract_month,
+            contract.strike, contract.right, contract.exchange, contract.currency, what_to_show) + " " + parameters.join(" ").as_str()
+    }
+
+    fn delay(&self, now: Instant, request_key: &str) -> Duration {
+        let mut ready_at = now;
+
+        if self.sent.len() >= MAX_REQUESTS_PER_WINDOW {
+            ready_at = ready_at.max(self.sent[self.sent.len() - MAX_REQUESTS_PER_WINDOW].0 + REQUEST_WINDOW);
+        }
+
+        if self.sent.len() >= MAX_REQUESTS_PER_BURST {
+            ready_at = ready_at.max(self.sent[self.sent.len() - MAX_REQUESTS_PER_BURST].0 + BURST_WINDOW);
+        }
+
+        for (sent_at, key) in self.sent.iter() {
+            if key == request_key {
+                ready_at = ready_at.max(*sent_at + IDENTICAL_REQUEST_INTERVAL);
+            }
+        }
+
+        ready_at - now
+    }
+
+    pub(crate) fn record(&mut self, now: Instant, request_key: &str) {
+        while let Some((sent_at, _)) = self.sent.front() {
+            if now.duration_since(*sent_at) < REQUEST_WINDOW {
+                break;
+            }
+            self.sent.pop_front();
+        }
+
+        self.sent.push_back((now, request_key.to_string()));
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum ChunkState {
+    Pending,
+    Done,
+    NoData,
+    Retry,
+    Failed(String),
+}
+
+#[derive(Debug, PartialEq)]
+enum HeadTimestampState {
+    Pending,
+    Known(i64),
+    Unknown,
+}
+
+// Downloads bars of a single contract between two points in time. The range is split in the
+// longest chunks IB allows for the bar size and requested from the end backwards down to the
+// head timestamp, throttled to the historical data pacing limits. Overlapping bars are merged
+// into one ordered series.
+//
+// download blocks and reads the client's events itself. Events that are not part of the
+// download are put back in the client's queue, in the order they came, when it returns.
+pub struct HistoricalDownloader {
+    req_id: i32,
+    contract: Contract,
+    bar_size: String,
+    what_to_show: String,
+    use_rth: i32,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    // used for daily bars, which only carry a date. Defaults to UTC, set it to the
+    // ContractDetails.time_zone_id of the contract to get the exchange's trading days.
+    pub time_zone_id: String,
+    bars: BTreeMap<i64, Bar>,
+    other_events: Vec<IncomingMessagesEnum>,
+}
+
+impl HistoricalDownloader {
+    pub fn new(req_id: i32, contract: Contract, bar_size: &str, what_to_show: &str, use_rth: i32, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        HistoricalDownloader {
+            req_id,
+            contract,
+            bar_size: bar_size.to_string(),
+            what_to_show: what_to_show.to_string(),
+            use_rth,
+            start,
+            end,
+            time_zone_id: "UTC".to_string(),
+            bars: BTreeMap::new(),
+            other_events: vec![],
+        }
+    }
+
+    pub fn download(&mut self, client: &mut IbClient) -> Result<Vec<Bar>, Box<dyn Error>> {
+        client.set_time_zone(self.req_id, self.time_zone_id.as_str());
+        let bars = self.download_chunks(client);
+        client.clear_time_zone(self.req_id);
+        self.return_other_events(client);
+        bars
+    }
+
+    fn download_chunks(&mut self, client: &mut IbClient) -> Result<Vec<Bar>, Box<dyn Error>> {
+        let (duration, _) = chunk_duration(self.bar_size.as_str())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("Unsupported bar size {}", self.bar_size)))?;
+
+        if let Some(head_timestamp) = self.head_timestamp(client)? {
+            self.start = self.start.max(head_timestamp);
+        }
+
+        let mut chunk_end = self.end;
+        let mut pacing_retries = 0;
+
+        while chunk_end > self.start {
+            let end_date_time = chunk_end.format("%Y%m%d-%H:%M:%S").to_string();
+            let request_key = Pacer::request_key(&self.contract, self.what_to_show.as_str(), &[end_date_time.as_str(), duration, self.bar_size.as_str()]);
+            thread::sleep(client.historical_pacer.delay(Instant::now(), request_key.as_str()));
+
+            client.req_historical_data(self.req_id, self.contract.clone(), end_date_time.as_str(), duration, self.bar_size.as_str(), self.what_to_show.as_str(), self.use_rth, 2, false, vec![]);
+
+            let mut chunk = vec![];
+            let state = loop {
+                let event = client.event_receiver.recv()?;
+                match self.on_event(event, &mut chunk) {
+                    ChunkState::Pending => continue,
+                    state => break state,
+                }
+            };
+
+            match state {
+                ChunkState::Retry => {
+                    pacing_retries += 1;
+                    if pacing_retries > MAX_PACING_RETRIES {
+                        return Err(Box::new(io::Error::other("Historical data pacing violations persist")));
+                    }
+                    thread::sleep(PACING_VIOLATION_BACKOFF * pacing_retries);
+                    continue;
+                },
+                ChunkState::Failed(message) => return Err(Box::new(io::Error::other(message))),
+                ChunkState::NoData => break,
+                ChunkState::Done | ChunkState::Pending => {}
+            }
+
+            pacing_retries = 0;
+
+            match self.merge(chunk)? {
+                Some(earliest) if earliest < chunk_end.timestamp() => chunk_end = Utc.timestamp(earliest, 0),
+                _ => break,
+            }
+        }
+
+        Ok(std::mem::take(&mut self.bars).into_values().collect())
+    }
+
+    // No data is available before the head timestamp, so there is no point in asking for it.
+    fn head_timestamp(&mut self, client: &mut IbClient) -> Result<Option<DateTime<Utc>>, Box<dyn Error>> {
+        let request_key = Pacer::request_key(&self.contract, self.what_to_show.as_str(), &["head timestamp"]);
+        thread::sleep(client.historical_pacer.delay(Instant::now(), request_key.as_str()));
+        client.req_head_timestamp(self.req_id, &self.contract, self.what_to_show.as_str(), self.use_rth, 2);
+
+        loop {
+            let event = client.event_receiver.recv()?;
+            match self.on_head_timestamp_event(event) {
+                HeadTimestampState::Pending => continue,
+                HeadTimestampState::Known(head_timestamp) => {
+                    client.cancel_head_timestamp(self.req_id);
+                    return Ok(Some(Utc.timestamp(head_timestamp, 0)));
+                },
+                HeadTimestampState::Unknown => return Ok(None),
+            }
+        }
+    }
+
+    // Events still queued go behind the ones read during the download to keep their order.
+    fn return_other_events(&mut self, client: &mut IbClient) {
+        self.other_events.extend(client.event_receiver.try_iter());
+
+        for event in self.other_events.drain(..) {
+            client.event_sender.send(event).unwrap_or_default();
+        }
+    }
+
+    fn on_event(&mut self, event: IncomingMessagesEnum, chunk: &mut Vec<Bar>) -> ChunkState {
+        match event {
+            IncomingMessagesEnum::HistoricalData(req_id, bar) if req_id == self.req_id => {
+                chunk.push(bar);
+                ChunkState::Pending
+            },
+            IncomingMessagesEnum::HistoricalDataEnd(req_id, _, _) if req_id == self.req_id => ChunkState::Done,
+            IncomingMessagesEnum::Error(req_id, code, message) if req_id == self.req_id => {
+                if code == PACING_VIOLATION && message.to_lowercase().contains("pacing violation") {
+                    ChunkState::Retry
+                } else if code == PACING_VIOLATION {
+                    // "HMDS query returned no data", the start of the available history
+                    ChunkState::NoData
+                } else {
+                    ChunkState::Failed(format!("{} {}", code, message))
+                }
+            },
+            // requests without keep_up_to_date are not replayed after a reconnection
+            IncomingMessagesEnum::Reconnected => {
+                self.other_events.push(IncomingMessagesEnum::Reconnected);
+                ChunkState::Retry
+            },
+            event => {
+                self.other_events.push(event);
+                ChunkState::Pending
+            }
+        }
+    }
+
+    // Without a head timestamp the download still works, it just stops at the first chunk
+    // without data.
+    fn on_head_timestamp_event(&mut self, event: IncomingMessagesEnum) -> HeadTimestampState {
+        match event {
+            IncomingMessagesEnum::HeadTimestamp(req_id, head_timestamp) if req_id == self.req_id => {
+                head_timestamp.parse().map_or(HeadTimestampState::Unknown, HeadTimestampState::Known)
+            },
+            IncomingMessagesEnum::Error(req_id, _, _) if req_id == self.req_id => HeadTimestampState::Unknown,
+            IncomingMessagesEnum::Reconnected => {
+                self.other_events.push(IncomingMessagesEnum::Reconnected);
+                HeadTimestampState::Unknown
+            },
+            event => {
+                self.other_events.push(event);
+                HeadTimestampState::Pending
+            }
+        }
+    }
+
+    // Adds the bars of a chunk that fall in the range and returns the earliest bar time. A bar
+    // whose time can not be read fails the download rather than ending it early.
+    fn merge(&mut self, chunk: Vec<Bar>) -> Result<Option<i64>, io::Error> {
+        let start = self.start.timestamp();
+        let end = self.end.timestamp();
+        let mut earliest = None;
+
+        for mut bar in chunk {
+            if !bar.has_time {
+                bar.localize(self.time_zone_id.as_str());
+            }
+            let time_int = match bar.date_time() {
+                Some(date_time) => date_time.timestamp(),
+                None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Bar time {:?} can not be read in time zone {}", bar.time_str, self.time_zone_id))),
+            };
+
+            earliest = Some(earliest.map_or(time_int, |earliest: i64| earliest.min(time_int)));
+
+            if time_int >= start && time_int < end {
+                self.bars.insert(time_int, bar);
+            }
+        }
+
+        Ok(earliest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(time_int: i64, close: f64) -> Bar {
+        let mut bar = Bar::new();
+        bar.time_int = time_int;
+        bar.has_time = true;
+        bar.close = close;
+        bar
+    }
+
+    fn downloader() -> HistoricalDownloader {
+        HistoricalDownloader::new(7, Contract::new(), "1 min", "TRADES", 1, Utc.timestamp(1_000, 0), Utc.timestamp(2_000, 0))
+    }
+
+    #[test]
+    fn pacer_spaces_identical_requests() {
+        let mut pacer = Pacer::default();
+        let now = Instant::now();
+        pacer.record(now, "a");
+
+        assert_eq!(pacer.delay(now + Duration::from_secs(5), "a"), Duration::from_secs(10));
+        assert_eq!(pacer.delay(now + Duration::from_secs(5), "b"), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn pacer_limits_bursts_and_windows() {
+        let mut pacer = Pacer::default();
+        let now = Instant::now();
+        for i in 0..MAX_REQUESTS_PER_BURST {
+            pacer.record(now, i.to_string().as_str());
+        }
+        assert_eq!(pacer.delay(now, "next"), BURST_WINDOW);
+
+        let mut pacer = Pacer::default();
+        for i in 0..MAX_REQUESTS_PER_WINDOW {
+            pacer.record(now + Duration::from_secs(3 * i as u64), i.to_string().as_str());
+        }
+        let last = now + Duration::from_secs(3 * (MAX_REQUESTS_PER_WINDOW as u64 - 1));
+        assert_eq!(pacer.delay(last, "next"), (now + REQUEST_WINDOW) - last);
+    }
+
+    #[test]
+    fn identical_requests_share_contract_data_and_parameters() {
+        let mut contract = Contract::new();
+        contract.symbol = "AAPL".to_string();
+        let key = Pacer::request_key(&contract, "TRADES", &["20220503-13:00:00", "1 D", "1 min"]);
+
+        assert_eq!(key, Pacer::request_key(&contract, "TRADES", &["20220503-13:00:00", "1 D", "1 min"]));
+        assert_ne!(key, Pacer::request_key(&contract, "MIDPOINT", &["20220503-13:00:00", "1 D", "1 min"]));
+        assert_ne!(key, Pacer::request_key(&contract, "TRADES", &["20220503-13:00:00", "1 D", "5 mins"]));
+
+        contract.symbol = "MSFT".to_string();
+        assert_ne!(key, Pacer::request_key(&contract, "TRADES", &["20220503-13:00:00", "1 D", "1 min"]));
+    }
+
+    #[test]
+    fn pacer_forgets_old_requests() {
+        let mut pacer = Pacer::default();
+        let now = Instant::now();
+        pacer.record(now, "a");
+        pacer.record(now + REQUEST_WINDOW, "b");
+
+        assert_eq!(pacer.sent.len(), 1);
+    }
+
+    #[test]
+    fn chunk_events_are_sorted_out() {
+        let mut downloader = downloader();
+        let mut chunk = vec![];
+
+        assert_eq!(downloader.on_event(IncomingMessagesEnum::HistoricalData(7, bar(1_500, 1.0)), &mut chunk), ChunkState::Pending);
+        assert_eq!(downloader.on_event(IncomingMessagesEnum::HistoricalData(8, bar(1_500, 1.0)), &mut chunk), ChunkState::Pending);
+        assert_eq!(downloader.on_event(IncomingMessagesEnum::Error(-1, 2104, "Market data farm connection is OK".to_string()), &mut chunk), ChunkState::Pending);
+        assert_eq!(downloader.on_event(IncomingMessagesEnum::HistoricalDataEnd(7, "".to_string(), "".to_string()), &mut chunk), ChunkState::Done);
+
+        assert_eq!(chunk.len(), 1);
+        assert_eq!(downloader.other_events.len(), 2);
+    }
+
+    #[test]
+    fn head_timestamp_is_read_from_its_own_request() {
+        let mut downloader = downloader();
+
+        assert_eq!(downloader.on_head_timestamp_event(IncomingMessagesEnum::HeadTimestamp(8, "1200".to_string())), HeadTimestampState::Pending);
+        assert_eq!(downloader.on_head_timestamp_event(IncomingMessagesEnum::HeadTimestamp(7, "1200".to_string())), HeadTimestampState::Known(1_200));
+        assert_eq!(downloader.on_head_timestamp_event(IncomingMessagesEnum::Error(7, 162, "No head time stamp".to_string())), HeadTimestampState::Unknown);
+        assert_eq!(downloader.other_events.len(), 1);
+    }
+
+    #[test]
+    fn other_events_go_back_to_the_client_in_order() {
+        let mut client = IbClient::new("127.0.0.1".to_string(), 7497, 0);
+        let mut downloader = downloader();
+        let mut chunk = vec![];
+
+        downloader.on_event(IncomingMessagesEnum::HistoricalData(8, bar(1_500, 1.0)), &mut chunk);
+        client.event_sender.send(IncomingMessagesEnum::HistoricalDataEnd(8, "".to_string(), "".to_string())).unwrap();
+        downloader.return_other_events(&mut client);
+
+        let events: Vec<IncomingMessagesEnum> = client.event_receiver.try_iter().collect();
+        assert!(matches!(events[..], [IncomingMessagesEnum::HistoricalData(8, _), IncomingMessagesEnum::HistoricalDataEnd(8, _, _)]));
+        assert!(downloader.other_events.is_empty());
+    }
+
+    #[test]
+    fn pacing_errors_are_retried() {
+        let mut downloader = downloader();
+        let mut chunk = vec![];
+
+        let pacing = IncomingMessagesEnum::Error(7, 162, "Historical Market Data Service error message:Historical data request pacing violation".to_string());
+        assert_eq!(downloader.on_event(pacing, &mut chunk), ChunkState::Retry);
+
+        let no_data = IncomingMessagesEnum::Error(7, 162, "Historical Market Data Service error message:HMDS query returned no data".to_string());
+        assert_eq!(downloader.on_event(no_data, &mut chunk), ChunkState::NoData);
+
+        let failure = IncomingMessagesEnum::Error(7, 200, "No security definition has been found for the request".to_string());
+        assert!(matches!(downloader.on_event(failure, &mut chunk), ChunkState::Failed(_)));
+    }
+
+    #[test]
+    fn overlapping_bars_are_merged_in_order() {
+        let mut downloader = downloader();
+
+        assert_eq!(downloader.merge(vec![bar(1_800, 3.0), bar(1_860, 4.0), bar(2_000, 9.0)]).unwrap(), Some(1_800));
+        assert_eq!(downloader.merge(vec![bar(900, 0.5), bar(1_740, 2.0), bar(1_800, 3.5)]).unwrap(), Some(900));
+
+        let bars: Vec<(i64, f64)> = downloader.bars.iter().map(|(time_int, bar)| (*time_int, bar.close)).collect();
+        assert_eq!(bars, vec![(1_740, 2.0), (1_800, 3.5), (1_860, 4.0)]);
+    }
+
+    #[test]
+    fn daily_bars_use_the_time_zone() {
+        let mut downloader = HistoricalDownloader::new(7, Contract::new(), "1 day", "TRADES", 1, Utc.timestamp(0, 0), Utc.timestamp(2_000_000_000, 0));
+        let mut daily = Bar::new();
+        daily.time_str = "20220503".to_string();
+
+        assert_eq!(downloader.merge(vec![daily]).unwrap(), Some(1651536000));
+        let mut unreadable = Bar::new();
+        unreadable.time_str = "20220504".to_string();
+        downloader.time_zone_id = "Nowhere/Special".to_string();
+        assert!(downloader.merge(vec![unreadable]).is_err());
+
+        assert!(chunk_duration("1 day").is_some());
+        assert!(chunk_duration("7 mins").is_none());
+    }
+}
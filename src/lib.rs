@@ -15,10 +15,17 @@ use crate::models::contract::Contract;
 use crate::models::order::Order;
 use crate::errors::client_errors;
 use crate::models::tag_value::TagValue;
+use crate::models::execution_filter::ExecutionFilter;
+use crate::enums::account_summary_tag::AccountSummaryTag;
+use crate::enums::exercise_action::ExerciseAction;
+use crate::enums::subscription::Subscription;
+use crate::historical_downloader::Pacer;
+use std::time::Instant;
 use crate::constants::{min_server_version, helper_constants};
 use std::error::Error;
 
 mod client_socket;
+mod frame_reader;
 mod api_parameter;
 mod decoder;
 pub mod models;
@@ -27,12 +34,17 @@ pub mod enums;
 mod order_decoder;
 mod traits;
 mod errors;
+pub mod historical_downloader;
+pub mod order_manager;
+#[cfg(feature = "async")]
+pub mod async_client;
 
 pub struct IbClient {
     pub event_receiver: Receiver<IncomingMessagesEnum>,
     event_sender: Sender<IncomingMessagesEnum>,
     client_socket: ClientSocket,
     is_connected: bool,
+    historical_pacer: Pacer,
 }
 
 impl IbClient {
@@ -46,11 +58,32 @@ impl IbClient {
             event_receiver,
             event_sender,
             is_connected: false,
+            historical_pacer: Pacer::default(),
         };
 
         ib_client
     }
 
+    #[cfg(feature = "async")]
+    pub(crate) fn new_buffered(client_id: i32, server_version: i32) -> IbClient {
+        let (event_sender, event_receiver) = unbounded();
+
+        let e_client_socket = ClientSocket::new_buffered(client_id, server_version, event_sender.clone());
+
+        IbClient {
+            client_socket: e_client_socket,
+            event_receiver,
+            event_sender,
+            is_connected: true,
+            historical_pacer: Pacer::default(),
+        }
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn take_outgoing(&mut self) -> Vec<Vec<u8>> {
+        self.client_socket.take_outbox()
+    }
+
     pub fn connect(&mut self) -> Result<(), Box<dyn Error>> {
         match self.client_socket.connect() {
             Ok(_) => {
@@ -97,7 +130,7 @@ impl IbClient {
         params_list.add_int(version);
         params_list.add_int(req_id);
 
-        if self.client_socket.server_version >= min_server_version::CONTRACT_CONID
+        if self.client_socket.server_version() >= min_server_version::CONTRACT_CONID
         {
             params_list.add_int(contract.con_id);
         }
@@ -108,28 +141,28 @@ impl IbClient {
         params_list.add_double(contract.strike);
         params_list.add_string(contract.right.as_str());
 
-        if self.client_socket.server_version >= 15 {
+        if self.client_socket.server_version() >= 15 {
             params_list.add_string(contract.multiplier.as_str());
         }
 
         params_list.add_string(contract.exchange.as_str());
 
-        if self.client_socket.server_version >= 14 {
+        if self.client_socket.server_version() >= 14 {
             params_list.add_string(contract.primary_exchange.as_str());
         }
 
         params_list.add_string(contract.currency.as_str());
 
-        if self.client_socket.server_version >= 2 {
+        if self.client_socket.server_version() >= 2 {
             params_list.add_string(contract.local_symbol.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::TRADING_CLASS {
+        if self.client_socket.server_version() >= min_server_version::TRADING_CLASS {
             params_list.add_string(contract.trading_class.as_str());
         }
 
 
-        if self.client_socket.server_version >= 8 && IbClient::strings_are_equal(helper_constants::BAG_SEC_TYPE, contract.sec_type.as_str()) {
+        if self.client_socket.server_version() >= 8 && IbClient::strings_are_equal(helper_constants::BAG_SEC_TYPE, contract.sec_type.as_str()) {
             if contract.combo_legs.len() == 0 {
                 params_list.add_int(0);
             }
@@ -144,7 +177,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version >= min_server_version::DELTA_NEUTRAL {
+        if self.client_socket.server_version() >= min_server_version::DELTA_NEUTRAL {
             match &contract.delta_neutral_contract {
                 Some(delta_neutral_contract) => {
                     params_list.add_bool(true);
@@ -158,34 +191,60 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version >= 31 {
+        if self.client_socket.server_version() >= 31 {
             params_list.add_string(generic_tick_list);
         }
 
-        if self.client_socket.server_version >= min_server_version::SNAPSHOT_MKT_DATA {
+        if self.client_socket.server_version() >= min_server_version::SNAPSHOT_MKT_DATA {
             params_list.add_bool(snapshot);
         }
 
-        if self.client_socket.server_version >= min_server_version::SMART_COMPONENTS {
+        if self.client_socket.server_version() >= min_server_version::SMART_COMPONENTS {
             params_list.add_bool(regulatory_snapshot);
         }
 
-        if self.client_socket.server_version >= min_server_version::LINKING {
+        if self.client_socket.server_version() >= min_server_version::LINKING {
             params_list.add_tag_value_vec(market_data_options);
         }
 
-        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+        let result = if snapshot {
+            self.client_socket.close_and_send(&mut params_list, length_pos)
+        } else {
+            self.send_subscription(OutgoingMessages::RequestMarketData, req_id, Subscription::MarketData, &mut params_list, length_pos)
+        };
+
+        result.unwrap_or_else(|err| {
             self.report_error(req_id, client_errors::FAIL_SEND_REQMKT, err.to_string().as_ref());
         });
     }
 
+    pub fn cancel_market_data(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        const VERSION: i32 = 2;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelMarketData as i32);
+        params_list.add_int(VERSION);
+        params_list.add_int(req_id);
+
+        self.client_socket.forget_replayable(OutgoingMessages::RequestMarketData, req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANMKT, err.to_string().as_str());
+        });
+    }
+
     pub fn req_scanner_subscription(&mut self, req_id: i32, subscription: ScannerSubscription) {
         if self.check_connection() == false {
             return;
         }
 
         const VERSION: i32 = 4;
-        let server_version = self.client_socket.server_version;
+        let server_version = self.client_socket.server_version();
         let mut params_list = ApiParameters::new();
         let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
@@ -241,40 +300,57 @@ impl IbClient {
             params_list.add_string(scanner_subscription_options);
         }
 
-        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+        self.send_subscription(OutgoingMessages::RequestScannerSubscription, req_id, Subscription::ScannerSubscription, &mut params_list, length_pos).unwrap_or_else(|err| {
             self.report_error(req_id, client_errors::FAIL_SEND_REQMKT, err.to_string().as_ref());
         });
     }
 
-    pub fn req_account_summary(&mut self, req_id: i32) {
+    // group is "All" or the name of a Financial Advisor group.
+    pub fn req_account_summary(&mut self, req_id: i32, group: &str, tags: &[AccountSummaryTag]) {
         if self.check_connection() == false {
             return;
         }
 
-        let group = "All";
-        let tags = "AccountType,NetLiquidation,TotalCashValue,SettledCash,AccruedCash,BuyingPower,EquityWithLoanValue,PreviousEquityWithLoanValue,GrossPositionValue,ReqTEquity,ReqTMargin,SMA,InitMarginReq,MaintMarginReq,AvailableFunds,ExcessLiquidity,Cushion,FullInitMarginReq,FullMaintMarginReq,FullAvailableFunds,FullExcessLiquidity,LookAheadNextChange,LookAheadInitMarginReq ,LookAheadMaintMarginReq,LookAheadAvailableFunds,LookAheadExcessLiquidity,HighestSeverity,DayTradesRemaining,Leverage";
+        if self.check_server_version(req_id, min_server_version::ACCT_SUMMARY, "It does not support account summary request.") == false {
+            return;
+        }
 
         const VERSION: i32 = 1;
-        if self.client_socket.is_connected == false {
-            return
+        let mut param_list = ApiParameters::new();
+        let length_pos = param_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        param_list.add_int(OutgoingMessages::RequestAccountSummary as i32);
+        param_list.add_int(VERSION);
+        param_list.add_int(req_id);
+        param_list.add_string(group);
+        param_list.add_string(AccountSummaryTag::join(tags).as_str());
+
+        self.send_subscription(OutgoingMessages::RequestAccountSummary, req_id, Subscription::AccountSummary, &mut param_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQACCOUNTDATA, err.to_string().as_ref());
+        });
+    }
+
+    pub fn cancel_account_summary(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
         }
 
-        if self.client_socket.server_version < min_server_version::ACCT_SUMMARY {
-            eprintln!("It does not support account summary request");
+        if self.check_server_version(req_id, min_server_version::ACCT_SUMMARY, "It does not support account summary cancellation.") == false {
             return;
         }
 
+        const VERSION: i32 = 1;
         let mut param_list = ApiParameters::new();
         let length_pos = param_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
-        param_list.add_int(OutgoingMessages::RequestAccountSummary as i32);
+        param_list.add_int(OutgoingMessages::CancelAccountSummary as i32);
         param_list.add_int(VERSION);
         param_list.add_int(req_id);
-        param_list.add_string(group);
-        param_list.add_string(tags);
+
+        self.client_socket.forget_replayable(OutgoingMessages::RequestAccountSummary, req_id);
 
         self.client_socket.close_and_send(&mut param_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_REQMKT, err.to_string().as_ref());
+            self.report_error(req_id, client_errors::FAIL_SEND_CANACCOUNTDATA, err.to_string().as_ref());
         });
     }
 
@@ -283,7 +359,7 @@ impl IbClient {
             return;
         }
 
-        let server_version = self.client_socket.server_version;
+        let server_version = self.client_socket.server_version();
         if contract.sec_id_type.is_empty() == false || contract.sec_id.is_empty() == false {
             if self.check_server_version(req_id, min_server_version::SEC_ID_TYPE, "It does not support secIdType not secId attributes") == false {
                 return;
@@ -359,90 +435,927 @@ impl IbClient {
             params_list.add_string(contract.sec_id.as_str());
         }
 
-        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_REQCONTRACT, err.to_string().as_str());
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQCONTRACT, err.to_string().as_str());
+        });
+    }
+
+    // Open orders placed by this client, answered with OpenOrder and OrderStatus events and an
+    // OpenOrderEnd.
+    pub fn req_open_orders(&mut self) {
+        self.send_open_orders_request(OutgoingMessages::RequestOpenOrders);
+    }
+
+    // Open orders of every API client and of TWS.
+    pub fn req_all_open_orders(&mut self) {
+        self.send_open_orders_request(OutgoingMessages::RequestAllOpenOrders);
+    }
+
+    // Only allowed for client id 0. With auto_bind set, orders placed in TWS are given an api
+    // order id (reported with OrderBound) and their updates are sent to this client from then on.
+    pub fn req_auto_open_orders(&mut self, auto_bind: bool) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        const VERSION: i32 = 1;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestAutoOpenOrders as i32);
+        params_list.add_int(VERSION);
+        params_list.add_bool(auto_bind);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(helper_constants::NO_VALID_ID, client_errors::FAIL_SEND_OORDER, err.to_string().as_str());
+        });
+    }
+
+    // Asks for a fresh NextValidId. num_ids is ignored by TWS but still part of the message.
+    pub fn req_ids(&mut self, num_ids: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        const VERSION: i32 = 1;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestIds as i32);
+        params_list.add_int(VERSION);
+        params_list.add_int(num_ids);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(helper_constants::NO_VALID_ID, client_errors::FAIL_GENERIC, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_global_cancel(&mut self) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        let req_id = 4;
+
+        if self.check_server_version(7, min_server_version::REQ_GLOBAL_CANCEL, "It does not support global cancel requests.") == false {
+            return;
+        }
+
+        const VERSION: i32 = 1;
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestGlobalCancel as i32);
+        params_list.add_int(VERSION);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQGLOBALCANCEL, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_cancel_order(&mut self, req_id: i32, order_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        const VERSION: i32 = 2;
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelOrder as i32);
+        params_list.add_int(VERSION);
+        params_list.add_int(order_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQGLOBALCANCEL, err.to_string().as_str());
+        });
+    }
+
+    // Orders that filled or were cancelled earlier in the day, answered with CompletedOrder
+    // events and a CompletedOrdersEnd. With api_only set, orders placed in TWS are left out.
+    pub fn req_completed_orders(&mut self, api_only: bool) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(helper_constants::NO_VALID_ID, min_server_version::COMPLETED_ORDERS, "It does not support completed orders requests.") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::ReqCompletedOrders as i32);
+        params_list.add_bool(api_only);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(helper_constants::NO_VALID_ID, client_errors::FAIL_SEND_REQCOMPLETEDORDERS, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_historical_data(&mut self, req_id: i32, contract: Contract, end_date_time: &str, duration: &str, bar_size_setting: &str, what_to_show: &str, use_rth: i32, date_format: i32, keep_up_to_date: bool, chart_options: Vec<TagValue>) {
+        let server_version = self.client_socket.server_version();
+
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, 16, "") == false {
+            return;
+        }
+
+        let pacing_key = Pacer::request_key(&contract, what_to_show, &[end_date_time, duration, bar_size_setting]);
+
+        if contract.trading_class.is_empty() == false || contract.con_id > 0
+        {
+            if self.check_server_version(req_id, min_server_version::TRADING_CLASS, "It does not support conId nor trading class parameters when requesting historical data.") == false {
+                return;
+            }
+        }
+
+        const VERSION: i32 = 6;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestHistoricalData as i32);
+
+        if server_version < min_server_version::SYNT_REALTIME_BARS
+        {
+            params_list.add_int(VERSION);
+        }
+
+        params_list.add_int(req_id);
+
+        if server_version >= min_server_version::TRADING_CLASS
+        {
+            params_list.add_int(contract.con_id);
+        }
+
+        params_list.add_string(contract.symbol.as_str());
+        params_list.add_string(contract.sec_type.as_str());
+        params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
+        params_list.add_double(contract.strike);
+        params_list.add_string(contract.right.as_str());
+        params_list.add_string(contract.multiplier.as_str());
+        params_list.add_string(contract.exchange.as_str());
+        params_list.add_string(contract.primary_exchange.as_str());
+        params_list.add_string(contract.currency.as_str());
+        params_list.add_string(contract.local_symbol.as_str());
+
+        if server_version >= min_server_version::TRADING_CLASS
+        {
+            params_list.add_string(contract.trading_class.as_str());
+        }
+
+        let include_expired = if contract.include_expired { 1 } else { 0 };
+        params_list.add_int(include_expired);
+
+        params_list.add_string(end_date_time);
+        params_list.add_string(bar_size_setting);
+
+        params_list.add_string(duration);
+        params_list.add_int(use_rth);
+        params_list.add_string(what_to_show);
+
+        params_list.add_int(date_format);
+
+        if IbClient::strings_are_equal(helper_constants::BAG_SEC_TYPE, contract.sec_type.as_str())
+        {
+            if contract.combo_legs.len() == 0
+            {
+                params_list.add_int(0);
+            }
+            else
+            {
+                params_list.add_int(contract.combo_legs.len() as i32);
+
+                for combo_leg in contract.combo_legs {
+                    params_list.add_int(combo_leg.con_id);
+                    params_list.add_int(combo_leg.ratio);
+                    params_list.add_string(combo_leg.action.as_str());
+                    params_list.add_string(combo_leg.exchange.as_str());
+                }
+            }
+        }
+
+        if server_version >= min_server_version::SYNT_REALTIME_BARS
+        {
+            params_list.add_bool(keep_up_to_date);
+        }
+
+        if server_version >= min_server_version::LINKING
+        {
+            params_list.add_tag_value_vec(chart_options);
+        }
+
+        // only a keep up to date request stays active after the initial bars
+        let result = if keep_up_to_date {
+            self.send_subscription(OutgoingMessages::RequestHistoricalData, req_id, Subscription::HistoricalData, &mut params_list, length_pos)
+        } else {
+            self.client_socket.close_and_send(&mut params_list, length_pos)
+        };
+
+        match result {
+            Ok(()) => self.historical_pacer.record(Instant::now(), pacing_key.as_str()),
+            Err(err) => self.report_error(req_id, client_errors::FAIL_SEND_REQHISTDATA, err.to_string().as_str()),
+        }
+    }
+
+    pub fn cancel_historical_data(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::HISTORICAL_DATA, "It does not support historical data query cancellation.") == false {
+            return;
+        }
+
+        const VERSION: i32 = 1;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelHistoricalData as i32);
+        params_list.add_int(VERSION);
+        params_list.add_int(req_id);
+
+        self.client_socket.forget_replayable(OutgoingMessages::RequestHistoricalData, req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANHISTDATA, err.to_string().as_str());
+        });
+    }
+
+    // Either start_date_time or end_date_time must be set, number_of_ticks is capped at 1000 by
+    // TWS. what_to_show is "TRADES", "MIDPOINT" or "BID_ASK".
+    pub fn req_historical_ticks(&mut self, req_id: i32, contract: &Contract, start_date_time: &str, end_date_time: &str, number_of_ticks: i32, what_to_show: &str, use_rth: i32, ignore_size: bool, misc_options: Vec<TagValue>) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::HISTORICAL_TICKS, "It does not support historical ticks requests.") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::ReqHistoricalTicks as i32);
+        params_list.add_int(req_id);
+
+        params_list.add_int(contract.con_id);
+        params_list.add_string(contract.symbol.as_str());
+        params_list.add_string(contract.sec_type.as_str());
+        params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
+        params_list.add_double(contract.strike);
+        params_list.add_string(contract.right.as_str());
+        params_list.add_string(contract.multiplier.as_str());
+        params_list.add_string(contract.exchange.as_str());
+        params_list.add_string(contract.primary_exchange.as_str());
+        params_list.add_string(contract.currency.as_str());
+        params_list.add_string(contract.local_symbol.as_str());
+        params_list.add_string(contract.trading_class.as_str());
+        params_list.add_bool(contract.include_expired);
+
+        params_list.add_string(start_date_time);
+        params_list.add_string(end_date_time);
+        params_list.add_int(number_of_ticks);
+        params_list.add_string(what_to_show);
+        params_list.add_int(use_rth);
+        params_list.add_bool(ignore_size);
+        params_list.add_tag_value_vec(misc_options);
+
+        match self.client_socket.close_and_send(&mut params_list, length_pos) {
+            Ok(()) => self.historical_pacer.record(Instant::now(), Pacer::request_key(contract, what_to_show, &["ticks", start_date_time, end_date_time]).as_str()),
+            Err(err) => self.report_error(req_id, client_errors::FAIL_SEND_REQHISTORICALTICKS, err.to_string().as_str()),
+        }
+    }
+
+    // The earliest date data is available for, answered with a HeadTimestamp event.
+    // format_date is 1 for "yyyyMMdd  HH:mm:ss" and 2 for epoch seconds.
+    pub fn req_head_timestamp(&mut self, req_id: i32, contract: &Contract, what_to_show: &str, use_rth: i32, format_date: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::REQ_HEAD_TIMESTAMP, "It does not support head time stamp requests.") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestHeadTimestamp as i32);
+        params_list.add_int(req_id);
+        params_list.add_int(contract.con_id);
+        params_list.add_string(contract.symbol.as_str());
+        params_list.add_string(contract.sec_type.as_str());
+        params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
+        params_list.add_double(contract.strike);
+        params_list.add_string(contract.right.as_str());
+        params_list.add_string(contract.multiplier.as_str());
+        params_list.add_string(contract.exchange.as_str());
+        params_list.add_string(contract.primary_exchange.as_str());
+        params_list.add_string(contract.currency.as_str());
+        params_list.add_string(contract.local_symbol.as_str());
+        params_list.add_string(contract.trading_class.as_str());
+        params_list.add_bool(contract.include_expired);
+        params_list.add_int(use_rth);
+        params_list.add_string(what_to_show);
+        params_list.add_int(format_date);
+
+        match self.client_socket.close_and_send(&mut params_list, length_pos) {
+            Ok(()) => self.historical_pacer.record(Instant::now(), Pacer::request_key(contract, what_to_show, &["head timestamp"]).as_str()),
+            Err(err) => self.report_error(req_id, client_errors::FAIL_SEND_REQHEADTIMESTAMP, err.to_string().as_str()),
+        }
+    }
+
+    pub fn cancel_head_timestamp(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::CANCEL_HEADTIMESTAMP, "It does not support head time stamp requests cancellation.") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelHeadTimestamp as i32);
+        params_list.add_int(req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANCELHEADTIMESTAMP, err.to_string().as_str());
+        });
+    }
+
+    // time_period is the span the distribution is computed over, e.g. "3 days" or "1 week".
+    pub fn req_histogram_data(&mut self, req_id: i32, contract: &Contract, use_rth: bool, time_period: &str) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::REQ_HISTOGRAM_DATA, "It does not support histogram requests.") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestHistogramData as i32);
+        params_list.add_int(req_id);
+        params_list.add_int(contract.con_id);
+        params_list.add_string(contract.symbol.as_str());
+        params_list.add_string(contract.sec_type.as_str());
+        params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
+        params_list.add_double(contract.strike);
+        params_list.add_string(contract.right.as_str());
+        params_list.add_string(contract.multiplier.as_str());
+        params_list.add_string(contract.exchange.as_str());
+        params_list.add_string(contract.primary_exchange.as_str());
+        params_list.add_string(contract.currency.as_str());
+        params_list.add_string(contract.local_symbol.as_str());
+        params_list.add_string(contract.trading_class.as_str());
+        params_list.add_bool(contract.include_expired);
+        params_list.add_bool(use_rth);
+        params_list.add_string(time_period);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQHISTOGRAMDATA, err.to_string().as_str());
+        });
+    }
+
+    pub fn cancel_histogram_data(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::REQ_HISTOGRAM_DATA, "It does not support histogram requests cancellation.") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelHistogramData as i32);
+        params_list.add_int(req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANCELHISTOGRAMDATA, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_real_time_bars(&mut self, req_id: i32, contract: Contract, bar_size: i32, what_to_show: &str, use_rth: bool, real_time_bar_options: Vec<TagValue>) {
+        let server_version = self.client_socket.server_version();
+
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::REAL_TIME_BARS, "It does not support real time bars.") == false {
+            return;
+        }
+
+        if contract.trading_class.is_empty() == false || contract.con_id > 0 {
+            if self.check_server_version(req_id, min_server_version::TRADING_CLASS, "It does not support ConId nor TradingClass parameters in reqRealTimeBars.") == false {
+                return;
+            }
+        }
+
+        const VERSION: i32 = 3;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestRealTimeBars as i32);
+        params_list.add_int(VERSION);
+        params_list.add_int(req_id);
+
+        // params_list.AddParameter contract fields
+        if server_version >= min_server_version::TRADING_CLASS
+        {
+            params_list.add_int(contract.con_id);
+        }
+
+        params_list.add_string(contract.symbol.as_str());
+        params_list.add_string(contract.sec_type.as_str());
+        params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
+        params_list.add_double(contract.strike);
+        params_list.add_string(contract.right.as_str());
+        params_list.add_string(contract.multiplier.as_str());
+        params_list.add_string(contract.exchange.as_str());
+        params_list.add_string(contract.primary_exchange.as_str());
+        params_list.add_string(contract.currency.as_str());
+        params_list.add_string(contract.local_symbol.as_str());
+
+        if server_version >= min_server_version::TRADING_CLASS
+        {
+            params_list.add_string(contract.trading_class.as_str());
+        }
+
+        params_list.add_int(bar_size);  // this parameter is not currently used
+        params_list.add_string(what_to_show);
+        params_list.add_bool(use_rth);
+
+        if server_version >= min_server_version::LINKING
+        {
+            params_list.add_tag_value_vec(real_time_bar_options);
+        }
+
+        self.send_subscription(OutgoingMessages::RequestRealTimeBars, req_id, Subscription::RealTimeBars, &mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQRTBARS, err.to_string().as_str());
+        });
+    }
+
+    pub fn cancel_real_time_bars(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::REAL_TIME_BARS, "It does not support realtime bar data query cancellation.") == false {
+            return;
+        }
+
+        const VERSION: i32 = 1;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelRealTimeBars as i32);
+        params_list.add_int(VERSION);
+        params_list.add_int(req_id);
+
+        self.client_socket.forget_replayable(OutgoingMessages::RequestRealTimeBars, req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANRTBARS, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_tick_by_tick(&mut self, req_id: i32, contract: Contract, tick_type: &str, number_of_ticks: i32, ignore_size: bool) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::TICK_BY_TICK, "It does not support tick-by-tick request") == false {
+            return;
+        }
+
+        if (number_of_ticks != 0 || ignore_size) && self.check_server_version(req_id, min_server_version::TICK_BY_TICK_IGNORE_SIZE, "It does not support ignoreSize and numberOfTicks parameters in tick-by-tick requests.") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::ReqTickByTickData as i32);
+        params_list.add_int(req_id);
+        params_list.add_int(contract.con_id);
+        params_list.add_string(contract.symbol.as_str());
+        params_list.add_string(contract.sec_type.as_str());
+        params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
+        params_list.add_double(contract.strike);
+        params_list.add_string(contract.right.as_str());
+        params_list.add_string(contract.multiplier.as_str());
+        params_list.add_string(contract.exchange.as_str());
+        params_list.add_string(contract.primary_exchange.as_str());
+        params_list.add_string(contract.currency.as_str());
+        params_list.add_string(contract.local_symbol.as_str());
+        params_list.add_string(contract.trading_class.as_str());
+        params_list.add_string(tick_type);
+
+        if self.client_socket.server_version() >= min_server_version::TICK_BY_TICK_IGNORE_SIZE
+        {
+            params_list.add_int(number_of_ticks);
+            params_list.add_bool(ignore_size);
+        }
+
+        self.send_subscription(OutgoingMessages::ReqTickByTickData, req_id, Subscription::TickByTick, &mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQTICKBYTICKDATA, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_pnl(&mut self, req_id: i32, account: &str, model_code: &str) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::PNL, "It does not support PNL request") == false {
+            return;
+        }
+
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::ReqPnL as i32);
+        params_list.add_int(req_id);
+        params_list.add_string(account);
+        params_list.add_string(model_code);
+
+        self.send_subscription(OutgoingMessages::ReqPnL, req_id, Subscription::PnL, &mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQPNL, err.to_string().as_str());
+        });
+    }
+
+    pub fn cancel_pnl(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::PNL, "It does not support PNL request") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelPnL as i32);
+        params_list.add_int(req_id);
+
+        self.client_socket.forget_replayable(OutgoingMessages::ReqPnL, req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANCELPNL, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_pnl_single(&mut self, req_id: i32, account: &str, model_code: &str, con_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::PNL, "It does not support PNL request") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::ReqPnLSingle as i32);
+        params_list.add_int(req_id);
+        params_list.add_string(account);
+        params_list.add_string(model_code);
+        params_list.add_int(con_id);
+
+        self.send_subscription(OutgoingMessages::ReqPnLSingle, req_id, Subscription::PnLSingle, &mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQPNLSINGLE, err.to_string().as_str());
+        });
+    }
+
+    pub fn cancel_pnl_single(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(req_id, min_server_version::PNL, "It does not support PNL request") == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelPnLSingle as i32);
+        params_list.add_int(req_id);
+
+        self.client_socket.forget_replayable(OutgoingMessages::ReqPnLSingle, req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANCELPNLSINGLE, err.to_string().as_str());
+        });
+    }
+
+    pub fn cancel_scanner_subscription(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelScannerSubscription as i32);
+        params_list.add_int(1);
+        params_list.add_int(req_id);
+
+        self.client_socket.forget_replayable(OutgoingMessages::RequestScannerSubscription, req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANSCANNER, err.to_string().as_str());
+        });
+    }
+
+    pub fn cancel_tick_subscription(&mut self, req_id: i32) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelTickByTickData as i32);
+        params_list.add_int(req_id);
+
+        self.client_socket.forget_replayable(OutgoingMessages::ReqTickByTickData, req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANCELTICKBYTICKDATA, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_market_depth(&mut self, req_id: i32, contract: &Contract, num_rows: i32, is_smart_depth: bool, mkt_depth_options: Vec<TagValue>) {
+        let server_version = self.client_socket.server_version();
+
+        if self.check_connection() == false {
+            return;
+        }
+
+        if contract.trading_class.is_empty() == false || contract.con_id > 0 {
+            if self.check_server_version(req_id, min_server_version::TRADING_CLASS, "It does not support ConId nor TradingClass parameters in reqMktDepth.") == false {
+                return;
+            }
+        }
+
+        if is_smart_depth && self.check_server_version(req_id, min_server_version::SMART_DEPTH, "It does not support SMART depth request.") == false {
+            return;
+        }
+
+        if contract.primary_exchange.is_empty() == false && self.check_server_version(req_id, min_server_version::MKT_DEPTH_PRIM_EXCHANGE, "It does not support primaryExch parameter in reqMktDepth.") == false {
+            return;
+        }
+
+        const VERSION: i32 = 5;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestMarketDepth as i32);
+        params_list.add_int(VERSION);
+        params_list.add_int(req_id);
+
+        // params_list.AddParameter contract fields
+        if server_version >= min_server_version::TRADING_CLASS
+        {
+            params_list.add_int(contract.con_id);
+        }
+
+        params_list.add_string(contract.symbol.as_str());
+        params_list.add_string(contract.sec_type.as_str());
+        params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
+        params_list.add_double(contract.strike);
+        params_list.add_string(contract.right.as_str());
+        params_list.add_string(contract.multiplier.as_str());
+        params_list.add_string(contract.exchange.as_str());
+
+        if server_version >= min_server_version::MKT_DEPTH_PRIM_EXCHANGE
+        {
+            params_list.add_string(contract.primary_exchange.as_str());
+        }
+
+        params_list.add_string(contract.currency.as_str());
+        params_list.add_string(contract.local_symbol.as_str());
+
+        if server_version >= min_server_version::TRADING_CLASS
+        {
+            params_list.add_string(contract.trading_class.as_str());
+        }
+
+        params_list.add_int(num_rows);
+
+        if server_version >= min_server_version::SMART_DEPTH
+        {
+            params_list.add_bool(is_smart_depth);
+        }
+
+        if server_version >= min_server_version::LINKING
+        {
+            params_list.add_tag_value_vec(mkt_depth_options);
+        }
+
+        self.send_subscription(OutgoingMessages::RequestMarketDepth, req_id, Subscription::MarketDepth { is_smart_depth }, &mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQMKTDEPTH, err.to_string().as_str());
+        });
+    }
+
+    pub fn cancel_market_depth(&mut self, req_id: i32, is_smart_depth: bool) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if is_smart_depth && self.check_server_version(req_id, min_server_version::SMART_DEPTH, "It does not support SMART depth cancel.") == false {
+            return;
+        }
+
+        const VERSION: i32 = 1;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::CancelMarketDepth as i32);
+        params_list.add_int(VERSION);
+        params_list.add_int(req_id);
+
+        if self.client_socket.server_version() >= min_server_version::SMART_DEPTH
+        {
+            params_list.add_bool(is_smart_depth);
+        }
+
+        self.client_socket.forget_replayable(OutgoingMessages::RequestMarketDepth, req_id);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_CANMKTDEPTH, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_executions(&mut self, req_id: i32, filter: &ExecutionFilter) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        const VERSION: i32 = 3;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestExecutions as i32);
+        params_list.add_int(VERSION);
+
+        if self.client_socket.server_version() >= min_server_version::EXECUTION_DATA_CHAIN
+        {
+            params_list.add_int(req_id);
+        }
+
+        params_list.add_int(filter.client_id);
+        params_list.add_string(filter.acct_code.as_str());
+        params_list.add_string(filter.time.as_str());
+        params_list.add_string(filter.symbol.as_str());
+        params_list.add_string(filter.sec_type.as_str());
+        params_list.add_string(filter.exchange.as_str());
+        params_list.add_string(filter.side.as_str());
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_EXEC, err.to_string().as_str());
+        });
+    }
+
+    pub fn req_positions(&mut self) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        if self.check_server_version(helper_constants::NO_VALID_ID, min_server_version::POSITIONS, "It does not support positions request.") == false {
+            return;
+        }
+
+        const VERSION: i32 = 1;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::RequestPositions as i32);
+        params_list.add_int(VERSION);
+
+        self.client_socket.close_and_send_replayable(OutgoingMessages::RequestPositions, helper_constants::POSITIONS_REPLAY_ID, &mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(helper_constants::NO_VALID_ID, client_errors::FAIL_SEND_REQPOSITIONS, err.to_string().as_str());
         });
     }
 
-    pub fn req_global_cancel(&mut self) {
+    pub fn cancel_positions(&mut self) {
         if self.check_connection() == false {
             return;
         }
 
-        let req_id = 4;
-
-        if self.check_server_version(7, min_server_version::REQ_GLOBAL_CANCEL, "It does not support global cancel requests.") == false {
+        if self.check_server_version(helper_constants::NO_VALID_ID, min_server_version::POSITIONS, "It does not support positions cancellation.") == false {
             return;
         }
 
         const VERSION: i32 = 1;
-
         let mut params_list = ApiParameters::new();
         let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
-        params_list.add_int(OutgoingMessages::RequestGlobalCancel as i32);
+        params_list.add_int(OutgoingMessages::CancelPositions as i32);
         params_list.add_int(VERSION);
 
+        self.client_socket.forget_replayable(OutgoingMessages::RequestPositions, helper_constants::POSITIONS_REPLAY_ID);
+
         self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_REQGLOBALCANCEL, err.to_string().as_str());
+            self.report_error(helper_constants::NO_VALID_ID, client_errors::FAIL_SEND_CANPOSITIONS, err.to_string().as_str());
         });
     }
 
-    pub fn req_cancel_order(&mut self, req_id: i32, order_id: i32) {
+    // Subscribes to (or, with subscribe set to false, unsubscribes from) the account values and
+    // portfolio of a single account. The account code is required for Financial Advisor accounts.
+    pub fn req_account_updates(&mut self, subscribe: bool, account: &str) {
         if self.check_connection() == false {
             return;
         }
 
         const VERSION: i32 = 2;
-
         let mut params_list = ApiParameters::new();
         let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
-        params_list.add_int(OutgoingMessages::CancelOrder as i32);
+        params_list.add_int(OutgoingMessages::RequestAccountData as i32);
         params_list.add_int(VERSION);
-        params_list.add_int(order_id);
+        params_list.add_bool(subscribe);
+        params_list.add_string(account);
 
-        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_REQGLOBALCANCEL, err.to_string().as_str());
+        let result = if subscribe {
+            self.client_socket.close_and_send_replayable(OutgoingMessages::RequestAccountData, helper_constants::ACCOUNT_UPDATES_REPLAY_ID, &mut params_list, length_pos)
+        } else {
+            self.client_socket.forget_replayable(OutgoingMessages::RequestAccountData, helper_constants::ACCOUNT_UPDATES_REPLAY_ID);
+            self.client_socket.close_and_send(&mut params_list, length_pos)
+        };
+
+        result.unwrap_or_else(|err| {
+            self.report_error(helper_constants::NO_VALID_ID, client_errors::FAIL_SEND_ACCT, err.to_string().as_str());
         });
     }
 
-    pub fn req_historical_data(&mut self, req_id: i32, contract: Contract, end_date_time: &str, duration: &str, bar_size_setting: &str, what_to_show: &str, use_rth: i32, date_format: i32, keep_up_to_date: bool, chart_options: Vec<TagValue>) {
-        let server_version = self.client_socket.server_version;
-
+    // fut_fop_exchange is only needed for options on futures, leave it empty for stock and
+    // index options to get the chains of every exchange.
+    pub fn req_sec_def_opt_params(&mut self, req_id: i32, underlying_symbol: &str, fut_fop_exchange: &str, underlying_sec_type: &str, underlying_con_id: i32) {
         if self.check_connection() == false {
             return;
         }
 
-        if self.check_server_version(req_id, 16, "") == false {
+        if self.check_server_version(req_id, min_server_version::SEC_DEF_OPT_PARAMS_REQ, "It does not support security definition option requests.") == false {
             return;
         }
 
-        if contract.trading_class.is_empty() == false || contract.con_id > 0
-        {
-            if self.check_server_version(req_id, min_server_version::TRADING_CLASS, "It does not support conId nor trading class parameters when requesting historical data.") == false {
-                return;
-            }
-        }
-
-        const VERSION: i32 = 6;
         let mut params_list = ApiParameters::new();
         let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
-        params_list.add_int(OutgoingMessages::RequestHistoricalData as i32);
+        params_list.add_int(OutgoingMessages::RequestSecurityDefinitionOptionalParameters as i32);
+        params_list.add_int(req_id);
+        params_list.add_string(underlying_symbol);
+        params_list.add_string(fut_fop_exchange);
+        params_list.add_string(underlying_sec_type);
+        params_list.add_int(underlying_con_id);
 
-        if server_version < min_server_version::SYNT_REALTIME_BARS
-        {
-            params_list.add_int(VERSION);
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(req_id, client_errors::FAIL_SEND_REQSECDEFOPTPARAMS, err.to_string().as_str());
+        });
+    }
+
+    // Both calculations are answered with a TickOptionComputation event for req_id with the
+    // CustOptionComputation tick type.
+    pub fn calculate_implied_volatility(&mut self, req_id: i32, contract: &Contract, option_price: f64, under_price: f64, implied_volatility_options: Vec<TagValue>) {
+        if self.check_connection() == false {
+            return;
         }
 
-        params_list.add_int(req_id);
+        if self.check_server_version(req_id, min_server_version::REQ_CALC_IMPLIED_VOLAT, "It does not support calculate implied volatility requests.") == false {
+            return;
+        }
 
-        if server_version >= min_server_version::TRADING_CLASS
-        {
-            params_list.add_int(contract.con_id);
+        if contract.trading_class.is_empty() == false && self.check_server_version(req_id, min_server_version::TRADING_CLASS, "It does not support tradingClass parameter in reqCalcImpliedVolat.") == false {
+            return;
         }
 
+        const VERSION: i32 = 3;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(OutgoingMessages::ReqCalcImpliedVolat as i32);
+        params_list.add_int(VERSION);
+        params_list.add_int(req_id);
+
+        params_list.add_int(contract.con_id);
         params_list.add_string(contract.symbol.as_str());
         params_list.add_string(contract.sec_type.as_str());
         params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
@@ -454,136 +1367,67 @@ impl IbClient {
         params_list.add_string(contract.currency.as_str());
         params_list.add_string(contract.local_symbol.as_str());
 
-        if server_version >= min_server_version::TRADING_CLASS
+        if self.client_socket.server_version() >= min_server_version::TRADING_CLASS
         {
             params_list.add_string(contract.trading_class.as_str());
         }
 
-        let include_expired = if contract.include_expired { 1 } else { 0 };
-        params_list.add_int(include_expired);
-
-        params_list.add_string(end_date_time);
-        params_list.add_string(bar_size_setting);
-
-        params_list.add_string(duration);
-        params_list.add_int(use_rth);
-        params_list.add_string(what_to_show);
-
-        params_list.add_int(date_format);
-
-        if IbClient::strings_are_equal(helper_constants::BAG_SEC_TYPE, contract.sec_type.as_str())
-        {
-            if contract.combo_legs.len() == 0
-            {
-                params_list.add_int(0);
-            }
-            else
-            {
-                params_list.add_int(contract.combo_legs.len() as i32);
-
-                for combo_leg in contract.combo_legs {
-                    params_list.add_int(combo_leg.con_id);
-                    params_list.add_int(combo_leg.ratio);
-                    params_list.add_string(combo_leg.action.as_str());
-                    params_list.add_string(combo_leg.exchange.as_str());
-                }
-            }
-        }
-
-        if server_version >= min_server_version::SYNT_REALTIME_BARS
-        {
-            params_list.add_bool(keep_up_to_date);
-        }
+        params_list.add_double(option_price);
+        params_list.add_double(under_price);
 
-        if server_version >= min_server_version::LINKING
+        if self.client_socket.server_version() >= min_server_version::LINKING
         {
-            params_list.add_tag_value_vec(chart_options);
+            params_list.add_tag_value_vec(implied_volatility_options);
         }
 
         self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_REQHISTDATA, err.to_string().as_str());
+            self.report_error(req_id, client_errors::FAIL_SEND_REQCALCIMPLIEDVOLAT, err.to_string().as_str());
         });
     }
 
-    pub fn req_real_time_bars(&mut self, req_id: i32, contract: Contract, bar_size: i32, what_to_show: &str, use_rth: bool, real_time_bar_options: Vec<TagValue>) {
-        let server_version = self.client_socket.server_version;
-
+    pub fn cancel_calculate_implied_volatility(&mut self, req_id: i32) {
         if self.check_connection() == false {
             return;
         }
 
-        if self.check_server_version(req_id, min_server_version::REAL_TIME_BARS, "It does not support real time bars.") == false {
+        if self.check_server_version(req_id, min_server_version::CANCEL_CALC_IMPLIED_VOLAT, "It does not support calculate implied volatility cancellation.") == false {
             return;
         }
 
-        if contract.trading_class.is_empty() == false || contract.con_id > 0 {
-            if self.check_server_version(req_id, min_server_version::TRADING_CLASS, "It does not support ConId nor TradingClass parameters in reqRealTimeBars.") == false {
-                return;
-            }
-        }
-
-        const VERSION: i32 = 3;
+        const VERSION: i32 = 1;
         let mut params_list = ApiParameters::new();
         let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
-        params_list.add_int(OutgoingMessages::RequestRealTimeBars as i32);
+        params_list.add_int(OutgoingMessages::CancelImpliedVolatility as i32);
         params_list.add_int(VERSION);
         params_list.add_int(req_id);
 
-        // params_list.AddParameter contract fields
-        if server_version >= min_server_version::TRADING_CLASS
-        {
-            params_list.add_int(contract.con_id);
-        }
-
-        params_list.add_string(contract.symbol.as_str());
-        params_list.add_string(contract.sec_type.as_str());
-        params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
-        params_list.add_double(contract.strike);
-        params_list.add_string(contract.right.as_str());
-        params_list.add_string(contract.multiplier.as_str());
-        params_list.add_string(contract.exchange.as_str());
-        params_list.add_string(contract.primary_exchange.as_str());
-        params_list.add_string(contract.currency.as_str());
-        params_list.add_string(contract.local_symbol.as_str());
-
-        if server_version >= min_server_version::TRADING_CLASS
-        {
-            params_list.add_string(contract.trading_class.as_str());
-        }
-
-        params_list.add_int(bar_size);  // this parameter is not currently used
-        params_list.add_string(what_to_show);
-        params_list.add_bool(use_rth);
-
-        if server_version >= min_server_version::LINKING
-        {
-            params_list.add_tag_value_vec(real_time_bar_options);
-        }
-
         self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_REQRTBARS, err.to_string().as_str());
+            self.report_error(req_id, client_errors::FAIL_SEND_CANCALCIMPLIEDVOLAT, err.to_string().as_str());
         });
     }
 
-    pub fn req_tick_by_tick(&mut self, req_id: i32, contract: Contract, tick_type: &str, number_of_ticks: i32, ignore_size: bool) {
+    pub fn calculate_option_price(&mut self, req_id: i32, contract: &Contract, volatility: f64, under_price: f64, option_price_options: Vec<TagValue>) {
         if self.check_connection() == false {
             return;
         }
 
-        if self.check_server_version(req_id, min_server_version::TICK_BY_TICK, "It does not support tick-by-tick request") == false {
+        if self.check_server_version(req_id, min_server_version::REQ_CALC_OPTION_PRICE, "It does not support calculate option price requests.") == false {
             return;
         }
 
-        if (number_of_ticks != 0 || ignore_size) && self.check_server_version(req_id, min_server_version::TICK_BY_TICK_IGNORE_SIZE, "It does not support ignoreSize and numberOfTicks parameters in tick-by-tick requests.") == false {
+        if contract.trading_class.is_empty() == false && self.check_server_version(req_id, min_server_version::TRADING_CLASS, "It does not support tradingClass parameter in reqCalcOptionPrice.") == false {
             return;
         }
 
+        const VERSION: i32 = 3;
         let mut params_list = ApiParameters::new();
         let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
-        params_list.add_int(OutgoingMessages::ReqTickByTickData as i32);
+        params_list.add_int(OutgoingMessages::ReqCalcOptionPrice as i32);
+        params_list.add_int(VERSION);
         params_list.add_int(req_id);
+
         params_list.add_int(contract.con_id);
         params_list.add_string(contract.symbol.as_str());
         params_list.add_string(contract.sec_type.as_str());
@@ -595,93 +1439,100 @@ impl IbClient {
         params_list.add_string(contract.primary_exchange.as_str());
         params_list.add_string(contract.currency.as_str());
         params_list.add_string(contract.local_symbol.as_str());
-        params_list.add_string(contract.trading_class.as_str());
-        params_list.add_string(tick_type);
 
-        if self.client_socket.server_version >= min_server_version::TICK_BY_TICK_IGNORE_SIZE
+        if self.client_socket.server_version() >= min_server_version::TRADING_CLASS
         {
-            params_list.add_int(number_of_ticks);
-            params_list.add_bool(ignore_size);
+            params_list.add_string(contract.trading_class.as_str());
+        }
+
+        params_list.add_double(volatility);
+        params_list.add_double(under_price);
+
+        if self.client_socket.server_version() >= min_server_version::LINKING
+        {
+            params_list.add_tag_value_vec(option_price_options);
         }
 
         self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_REQTICKBYTICKDATA, err.to_string().as_str());
+            self.report_error(req_id, client_errors::FAIL_SEND_REQCALCOPTIONPRICE, err.to_string().as_str());
         });
     }
 
-    pub fn req_pnl(&mut self, req_id: i32, account: &str, model_code: &str) {
+    pub fn cancel_calculate_option_price(&mut self, req_id: i32) {
         if self.check_connection() == false {
             return;
         }
 
-        if self.check_server_version(req_id, min_server_version::PNL, "It does not support PNL request") == false {
+        if self.check_server_version(req_id, min_server_version::CANCEL_CALC_OPTION_PRICE, "It does not support calculate option price cancellation.") == false {
             return;
         }
 
-
+        const VERSION: i32 = 1;
         let mut params_list = ApiParameters::new();
         let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
-        params_list.add_int(OutgoingMessages::ReqPnL as i32);
+        params_list.add_int(OutgoingMessages::CancelOptionPrice as i32);
+        params_list.add_int(VERSION);
         params_list.add_int(req_id);
-        params_list.add_string(account);
-        params_list.add_string(model_code);
 
         self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_REQTICKBYTICKDATA, err.to_string().as_str());
+            self.report_error(req_id, client_errors::FAIL_SEND_CANCALCOPTIONPRICE, err.to_string().as_str());
         });
     }
 
-    pub fn cancel_pnl(&mut self, req_id: i32) {
+    // With override_natural_action set, TWS exercises or lapses the position even when that goes
+    // against what would happen automatically at expiry (e.g. exercising an out of the money option).
+    pub fn exercise_options(&mut self, req_id: i32, contract: &Contract, action: ExerciseAction, quantity: i32, account: &str, override_natural_action: bool) {
         if self.check_connection() == false {
             return;
         }
 
-        if self.check_server_version(req_id, min_server_version::PNL, "It does not support PNL request") == false {
+        if self.check_server_version(req_id, min_server_version::EXERCISE_OPTIONS, "It does not support options exercise from the API.") == false {
             return;
         }
 
-        let mut params_list = ApiParameters::new();
-        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
-
-        params_list.add_int(OutgoingMessages::CancelPnL as i32);
-        params_list.add_int(req_id);
-
-        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_REQTICKBYTICKDATA, err.to_string().as_str());
-        });
-    }
-
-    pub fn cancel_scanner_subscription(&mut self, req_id: i32) {
-        if self.check_connection() == false {
-            return;
+        if contract.trading_class.is_empty() == false || contract.con_id > 0 {
+            if self.check_server_version(req_id, min_server_version::TRADING_CLASS, "It does not support conId, multiplier and tradingClass parameters in exerciseOptions.") == false {
+                return;
+            }
         }
 
+        const VERSION: i32 = 2;
         let mut params_list = ApiParameters::new();
         let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
-        params_list.add_int(OutgoingMessages::CancelScannerSubscription as i32);
-        params_list.add_int(1);
+        params_list.add_int(OutgoingMessages::ExerciseOptions as i32);
+        params_list.add_int(VERSION);
         params_list.add_int(req_id);
 
-        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_CANSCANNER, err.to_string().as_str());
-        });
-    }
-
-    pub fn cancel_tick_subscription(&mut self, req_id: i32) {
-        if self.check_connection() == false {
-            return;
+        if self.client_socket.server_version() >= min_server_version::TRADING_CLASS
+        {
+            params_list.add_int(contract.con_id);
         }
 
-        let mut params_list = ApiParameters::new();
-        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+        params_list.add_string(contract.symbol.as_str());
+        params_list.add_string(contract.sec_type.as_str());
+        params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
+        params_list.add_double(contract.strike);
+        params_list.add_string(contract.right.as_str());
+        params_list.add_string(contract.multiplier.as_str());
+        params_list.add_string(contract.exchange.as_str());
+        params_list.add_string(contract.currency.as_str());
+        params_list.add_string(contract.local_symbol.as_str());
 
-        params_list.add_int(OutgoingMessages::CancelTickByTickData as i32);
-        params_list.add_int(req_id);
+        if self.client_socket.server_version() >= min_server_version::TRADING_CLASS
+        {
+            params_list.add_string(contract.trading_class.as_str());
+        }
+
+        params_list.add_int(action as i32);
+        params_list.add_int(quantity);
+        params_list.add_string(account);
+        params_list.add_bool(override_natural_action);
 
+        // the reference client has no exercise specific code and reports market data ones
         self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
-            self.report_error(req_id, client_errors::FAIL_SEND_CANCELTICKBYTICKDATA, err.to_string().as_str());
+            self.report_error(req_id, client_errors::FAIL_SEND_REQMKT, err.to_string().as_str());
         });
     }
 
@@ -699,19 +1550,19 @@ impl IbClient {
             return
         }
 
-        let message_version = if self.client_socket.server_version < min_server_version::NOT_HELD { 27 } else { 45 };
+        let message_version = if self.client_socket.server_version() < min_server_version::NOT_HELD { 27 } else { 45 };
         let mut params_list = ApiParameters::new();
         let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
 
         params_list.add_int(OutgoingMessages::PlaceOrder as i32);
 
-        if self.client_socket.server_version < min_server_version::ORDER_CONTAINER {
+        if self.client_socket.server_version() < min_server_version::ORDER_CONTAINER {
             params_list.add_int(message_version);
         }
 
         params_list.add_int(order_id);
 
-        if self.client_socket.server_version >= min_server_version::PLACE_ORDER_CONID {
+        if self.client_socket.server_version() >= min_server_version::PLACE_ORDER_CONID {
             params_list.add_int(contract.con_id);
         }
 
@@ -720,23 +1571,23 @@ impl IbClient {
         params_list.add_string(contract.last_trade_date_or_contract_month.as_str());
         params_list.add_double(contract.strike);
         params_list.add_string(contract.right.as_str());
-        if self.client_socket.server_version >= 15 {
+        if self.client_socket.server_version() >= 15 {
             params_list.add_string(contract.multiplier.as_str());
         }
         params_list.add_string(contract.exchange.as_str());
-        if self.client_socket.server_version >= 14 {
+        if self.client_socket.server_version() >= 14 {
             params_list.add_string(contract.primary_exchange.as_str());
         }
         params_list.add_string(contract.currency.as_str());
-        if self.client_socket.server_version >= 2 {
+        if self.client_socket.server_version() >= 2 {
             params_list.add_string(contract.local_symbol.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::TRADING_CLASS
+        if self.client_socket.server_version() >= min_server_version::TRADING_CLASS
         {
             params_list.add_string(contract.trading_class.as_str());
         }
-        if self.client_socket.server_version >= min_server_version::SEC_ID_TYPE
+        if self.client_socket.server_version() >= min_server_version::SEC_ID_TYPE
         {
             params_list.add_string(contract.sec_id_type.as_str());
             params_list.add_string(contract.sec_id.as_str());
@@ -745,7 +1596,7 @@ impl IbClient {
         // params_list.add_string main order fields
         params_list.add_string(order.action.as_str());
 
-        if self.client_socket.server_version >= min_server_version::FRACTIONAL_POSITIONS {
+        if self.client_socket.server_version() >= min_server_version::FRACTIONAL_POSITIONS {
             params_list.add_double(order.total_quantity);
         }
         else {
@@ -753,7 +1604,7 @@ impl IbClient {
         }
 
         params_list.add_string(order.order_type.as_str());
-        if self.client_socket.server_version < min_server_version::ORDER_COMBO_LEGS_PRICE
+        if self.client_socket.server_version() < min_server_version::ORDER_COMBO_LEGS_PRICE
         {
             let val = if order.lmt_price == f64::MAX { 0.0 } else { order.lmt_price };
             params_list.add_double(val);
@@ -762,7 +1613,7 @@ impl IbClient {
         {
             params_list.add_double_max(order.lmt_price);
         }
-        if self.client_socket.server_version < min_server_version::TRAILING_PERCENT
+        if self.client_socket.server_version() < min_server_version::TRAILING_PERCENT
         {
             let val = if order.aux_price == f64::MAX { 0.0 } else { order.aux_price };
             params_list.add_double(val);
@@ -780,18 +1631,18 @@ impl IbClient {
         params_list.add_int(order.origin as i32);
         params_list.add_string(order.order_ref.as_str());
         params_list.add_bool(order.transmit);
-        if self.client_socket.server_version >= 4
+        if self.client_socket.server_version() >= 4
         {
             params_list.add_int(order.parent_id);
         }
 
-        if self.client_socket.server_version >= 5
+        if self.client_socket.server_version() >= 5
         {
             params_list.add_bool(order.block_order);
             params_list.add_bool(order.sweep_to_fill);
             params_list.add_int(order.display_size);
             params_list.add_int(order.trigger_method);
-            if self.client_socket.server_version < 38
+            if self.client_socket.server_version() < 38
             {
                 // will never happen
                 params_list.add_bool(/* order.ignoreRth */ false);
@@ -802,14 +1653,14 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version >= 7
+        if self.client_socket.server_version() >= 7
         {
             params_list.add_bool(order.hidden);
         }
 
         // params_list.add_string combo legs for BAG requests
         let is_bag = IbClient::strings_are_equal(helper_constants::BAG_SEC_TYPE, &contract.sec_type);
-        if self.client_socket.server_version >= 8 && is_bag
+        if self.client_socket.server_version() >= 8 && is_bag
         {
             if contract.combo_legs.len() == 0
             {
@@ -825,12 +1676,12 @@ impl IbClient {
                     params_list.add_string(combo_leg.exchange.as_str());
                     params_list.add_int((combo_leg.open_close).clone() as i32);
 
-                    if self.client_socket.server_version >= min_server_version::SSHORT_COMBO_LEGS
+                    if self.client_socket.server_version() >= min_server_version::SSHORT_COMBO_LEGS
                     {
                         params_list.add_int(combo_leg.short_sale_slot);
                         params_list.add_string(combo_leg.designated_location.as_str());
                     }
-                    if self.client_socket.server_version >= min_server_version::SSHORTX_OLD
+                    if self.client_socket.server_version() >= min_server_version::SSHORTX_OLD
                     {
                         params_list.add_int(combo_leg.exempt_code);
                     }
@@ -839,7 +1690,7 @@ impl IbClient {
         }
 
         // add order combo legs for BAG requests
-        if self.client_socket.server_version >= min_server_version::ORDER_COMBO_LEGS_PRICE && is_bag
+        if self.client_socket.server_version() >= min_server_version::ORDER_COMBO_LEGS_PRICE && is_bag
         {
             if order.order_combo_legs.len() == 0
             {
@@ -855,7 +1706,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version >= min_server_version::SMART_COMBO_ROUTING_PARAMS && is_bag
+        if self.client_socket.server_version() >= min_server_version::SMART_COMBO_ROUTING_PARAMS && is_bag
         {
             params_list.add_int(order.smart_combo_routing_params.len() as i32);
             if order.smart_combo_routing_params.len() > 0
@@ -867,28 +1718,28 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version >= 9
+        if self.client_socket.server_version() >= 9
         {
             // params_list.add_string deprecated sharesAllocation field
             params_list.add_string("");
         }
 
-        if self.client_socket.server_version >= 10
+        if self.client_socket.server_version() >= 10
         {
             params_list.add_double(order.discretionary_amt);
         }
 
-        if self.client_socket.server_version >= 11
+        if self.client_socket.server_version() >= 11
         {
             params_list.add_string(order.good_after_time.as_str());
         }
 
-        if self.client_socket.server_version >= 12
+        if self.client_socket.server_version() >= 12
         {
             params_list.add_string(order.good_till_date.as_str());
         }
 
-        if self.client_socket.server_version >= 13
+        if self.client_socket.server_version() >= 13
         {
             params_list.add_string(order.fa_group.as_str());
             params_list.add_string(order.fa_method.as_str());
@@ -896,24 +1747,24 @@ impl IbClient {
             params_list.add_string(order.fa_profile.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::MODELS_SUPPORT
+        if self.client_socket.server_version() >= min_server_version::MODELS_SUPPORT
         {
             params_list.add_string(order.model_code.as_str());
         }
 
-        if self.client_socket.server_version >= 18
+        if self.client_socket.server_version() >= 18
         { // institutional short sale slot fields.
             params_list.add_int(order.short_sale_slot);      // 0 only for retail, 1 or 2 only for institution.
             params_list.add_string(order.designated_location.as_str()); // only populate when order.shortSaleSlot = 2.
         }
-        if self.client_socket.server_version >= min_server_version::SSHORTX_OLD
+        if self.client_socket.server_version() >= min_server_version::SSHORTX_OLD
         {
             params_list.add_int(order.exempt_code);
         }
-        if self.client_socket.server_version >= 19
+        if self.client_socket.server_version() >= 19
         {
             params_list.add_int(order.oca_type);
-            if self.client_socket.server_version < 38
+            if self.client_socket.server_version() < 38
             {
                 // will never happen
                 params_list.add_bool( /* order.rthOnly */ false);
@@ -931,22 +1782,22 @@ impl IbClient {
             params_list.add_double_max(order.stock_ref_price);
             params_list.add_double_max(order.delta);
             // Volatility orders had specific watermark price attribs in server version 26
-            let lower = if self.client_socket.server_version == 26 && order.order_type == "VOL".to_string() { f64::MAX } else { order.stock_range_lower };
-            let upper = if self.client_socket.server_version == 26 && order.order_type == "VOL".to_string() { f64::MAX } else { order.stock_range_upper };
+            let lower = if self.client_socket.server_version() == 26 && order.order_type == "VOL".to_string() { f64::MAX } else { order.stock_range_lower };
+            let upper = if self.client_socket.server_version() == 26 && order.order_type == "VOL".to_string() { f64::MAX } else { order.stock_range_upper };
             params_list.add_double_max(lower);
             params_list.add_double_max(upper);
         }
 
-        if self.client_socket.server_version >= 22
+        if self.client_socket.server_version() >= 22
         {
             params_list.add_bool(order.override_percentage_constraints);
         }
 
-        if self.client_socket.server_version >= 26
+        if self.client_socket.server_version() >= 26
         { // Volatility orders
             params_list.add_double_max(order.volatility);
             params_list.add_int_max(order.volatility_type);
-            if self.client_socket.server_version < 28
+            if self.client_socket.server_version() < 28
             {
                 let is_delta_neutral_type_mkt = IbClient::strings_are_equal("MKT", &order.delta_neutral_order_type);
                 params_list.add_bool(is_delta_neutral_type_mkt);
@@ -956,7 +1807,7 @@ impl IbClient {
                 params_list.add_string(order.delta_neutral_order_type.as_str());
                 params_list.add_double_max(order.delta_neutral_aux_price);
 
-                if self.client_socket.server_version >= min_server_version::DELTA_NEUTRAL_CONID && order.delta_neutral_order_type.is_empty() == false
+                if self.client_socket.server_version() >= min_server_version::DELTA_NEUTRAL_CONID && order.delta_neutral_order_type.is_empty() == false
                 {
                     params_list.add_int(order.delta_neutral_con_id);
                     params_list.add_string(order.delta_neutral_settling_firm.as_str());
@@ -964,7 +1815,7 @@ impl IbClient {
                     params_list.add_string(order.delta_neutral_clearing_intent.as_str());
                 }
 
-                if self.client_socket.server_version >= min_server_version::DELTA_NEUTRAL_OPEN_CLOSE && order.delta_neutral_order_type.is_empty() == false
+                if self.client_socket.server_version() >= min_server_version::DELTA_NEUTRAL_OPEN_CLOSE && order.delta_neutral_order_type.is_empty() == false
                 {
                     params_list.add_string(order.delta_neutral_open_close.as_str());
                     params_list.add_bool(order.delta_neutral_short_sale);
@@ -973,7 +1824,7 @@ impl IbClient {
                 }
             }
             params_list.add_int(order.continuous_update);
-            if self.client_socket.server_version == 26
+            if self.client_socket.server_version() == 26
             {
                 // Volatility orders had specific watermark price attribs in server version 26
                 let lower = if order.order_type == "VOL".to_string() { order.stock_range_lower } else { f64::MAX };
@@ -984,19 +1835,19 @@ impl IbClient {
             params_list.add_int_max(order.reference_price_type);
         }
 
-        if self.client_socket.server_version >= 30
+        if self.client_socket.server_version() >= 30
         { // TRAIL_STOP_LIMIT stop price
             params_list.add_double_max(order.trail_stop_price);
         }
 
-        if self.client_socket.server_version >= min_server_version::TRAILING_PERCENT
+        if self.client_socket.server_version() >= min_server_version::TRAILING_PERCENT
         {
             params_list.add_double_max(order.trailing_percent);
         }
 
-        if self.client_socket.server_version >= min_server_version::SCALE_ORDERS
+        if self.client_socket.server_version() >= min_server_version::SCALE_ORDERS
         {
-            if self.client_socket.server_version >= min_server_version::SCALE_ORDERS2
+            if self.client_socket.server_version() >= min_server_version::SCALE_ORDERS2
             {
                 params_list.add_int_max(order.scale_init_level_size);
                 params_list.add_int_max(order.scale_subs_level_size);
@@ -1010,7 +1861,7 @@ impl IbClient {
             params_list.add_double_max(order.scale_price_increment);
         }
 
-        if self.client_socket.server_version >= min_server_version::SCALE_ORDERS3 && order.scale_price_increment > 0.0 && order.scale_price_increment != f64::MAX
+        if self.client_socket.server_version() >= min_server_version::SCALE_ORDERS3 && order.scale_price_increment > 0.0 && order.scale_price_increment != f64::MAX
         {
             params_list.add_double_max(order.scale_price_adjust_value);
             params_list.add_int_max(order.scale_price_adjust_interval);
@@ -1021,14 +1872,14 @@ impl IbClient {
             params_list.add_bool(order.scale_random_percent);
         }
 
-        if self.client_socket.server_version >= min_server_version::SCALE_TABLE
+        if self.client_socket.server_version() >= min_server_version::SCALE_TABLE
         {
             params_list.add_string(order.scale_table.as_str());
             params_list.add_string(order.active_start_time.as_str());
             params_list.add_string(order.active_stop_time.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::HEDGE_ORDERS
+        if self.client_socket.server_version() >= min_server_version::HEDGE_ORDERS
         {
             params_list.add_string(order.hedge_type.as_str());
             if order.hedge_type.is_empty() == false
@@ -1037,23 +1888,23 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version >= min_server_version::OPT_OUT_SMART_ROUTING
+        if self.client_socket.server_version() >= min_server_version::OPT_OUT_SMART_ROUTING
         {
             params_list.add_bool(order.opt_out_smart_routing);
         }
 
-        if self.client_socket.server_version >= min_server_version::PTA_ORDERS
+        if self.client_socket.server_version() >= min_server_version::PTA_ORDERS
         {
             params_list.add_string(order.clearing_account.as_str());
             params_list.add_string(order.clearing_intent.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::NOT_HELD
+        if self.client_socket.server_version() >= min_server_version::NOT_HELD
         {
             params_list.add_bool(order.not_held);
         }
 
-        if self.client_socket.server_version >= min_server_version::DELTA_NEUTRAL
+        if self.client_socket.server_version() >= min_server_version::DELTA_NEUTRAL
         {
             match contract.delta_neutral_contract {
                 Some(res) => {
@@ -1070,7 +1921,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version >= min_server_version::ALGO_ORDERS
+        if self.client_socket.server_version() >= min_server_version::ALGO_ORDERS
         {
             params_list.add_string(order.algo_strategy.as_str());
             if order.algo_strategy.is_empty() == false
@@ -1083,17 +1934,17 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version >= min_server_version::ALGO_ID
+        if self.client_socket.server_version() >= min_server_version::ALGO_ID
         {
             params_list.add_string(order.algo_id.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::WHAT_IF_ORDERS
+        if self.client_socket.server_version() >= min_server_version::WHAT_IF_ORDERS
         {
             params_list.add_bool(order.what_if);
         }
 
-        if self.client_socket.server_version >= min_server_version::LINKING
+        if self.client_socket.server_version() >= min_server_version::LINKING
         {
             let mut tag_values = "".to_string();
 
@@ -1104,18 +1955,18 @@ impl IbClient {
             params_list.add_string(tag_values.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::ORDER_SOLICITED
+        if self.client_socket.server_version() >= min_server_version::ORDER_SOLICITED
         {
             params_list.add_bool(order.solicited);
         }
 
-        if self.client_socket.server_version >= min_server_version::RANDOMIZE_SIZE_AND_PRICE
+        if self.client_socket.server_version() >= min_server_version::RANDOMIZE_SIZE_AND_PRICE
         {
             params_list.add_bool(order.randomize_size);
             params_list.add_bool(order.randomize_price);
         }
 
-        if self.client_socket.server_version >= min_server_version::PEGGED_TO_BENCHMARK
+        if self.client_socket.server_version() >= min_server_version::PEGGED_TO_BENCHMARK
         {
             if order.order_type == "PEG BENCH".to_string()
             {
@@ -1148,50 +1999,50 @@ impl IbClient {
             params_list.add_int(order.adjustable_trailing_unit);
         }
 
-        if self.client_socket.server_version >= min_server_version::EXT_OPERATOR
+        if self.client_socket.server_version() >= min_server_version::EXT_OPERATOR
         {
             params_list.add_string(order.ext_operator.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::SOFT_DOLLAR_TIER
+        if self.client_socket.server_version() >= min_server_version::SOFT_DOLLAR_TIER
         {
             params_list.add_string(order.soft_dollar_tier.name.as_str());
             params_list.add_string(order.soft_dollar_tier.val.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::CASH_QTY
+        if self.client_socket.server_version() >= min_server_version::CASH_QTY
         {
             params_list.add_double(order.cash_qty);
         }
 
-        if self.client_socket.server_version >= min_server_version::DECISION_MAKER
+        if self.client_socket.server_version() >= min_server_version::DECISION_MAKER
         {
             params_list.add_string(order.mifid2decision_maker.as_str());
             params_list.add_string(order.mifid2decision_algo.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::MIFID_EXECUTION
+        if self.client_socket.server_version() >= min_server_version::MIFID_EXECUTION
         {
             params_list.add_string(order.mifid2execution_trader.as_str());
             params_list.add_string(order.mifid2execution_algo.as_str());
         }
 
-        if self.client_socket.server_version >= min_server_version::AUTO_PRICE_FOR_HEDGE
+        if self.client_socket.server_version() >= min_server_version::AUTO_PRICE_FOR_HEDGE
         {
             params_list.add_bool(order.dont_use_auto_price_for_hedge);
         }
 
-        if self.client_socket.server_version >= min_server_version::ORDER_CONTAINER
+        if self.client_socket.server_version() >= min_server_version::ORDER_CONTAINER
         {
             params_list.add_bool(order.is_oms_container);
         }
 
-        if self.client_socket.server_version >= min_server_version::D_PEG_ORDERS
+        if self.client_socket.server_version() >= min_server_version::D_PEG_ORDERS
         {
             params_list.add_bool(order.discretionary_up_to_limit_price);
         }
 
-        if self.client_socket.server_version >= min_server_version::PRICE_MGMT_ALGO
+        if self.client_socket.server_version() >= min_server_version::PRICE_MGMT_ALGO
         {
             params_list.add_bool(order.use_price_mgmt_algo);
         }
@@ -1201,6 +2052,61 @@ impl IbClient {
         })
     }
 
+    // Bars of req_id given in local time are placed in this time zone so the decoder can fill in
+    // their time_int. The client does not look the zone up: pass the time_zone_id of the
+    // contract's ContractDetails, requested beforehand. Without it such bars keep has_time unset.
+    pub fn set_time_zone(&mut self, req_id: i32, time_zone_id: &str) {
+        self.client_socket.time_zones.lock().unwrap().insert(req_id, time_zone_id.to_string());
+    }
+
+    pub fn clear_time_zone(&mut self, req_id: i32) {
+        self.client_socket.time_zones.lock().unwrap().remove(&req_id);
+    }
+
+    // Sends a streaming request and tracks it once it went out.
+    fn send_subscription(&mut self, request: OutgoingMessages, req_id: i32, subscription: Subscription, params: &mut ApiParameters, length_pos: u32) -> Result<(), Box<dyn Error>> {
+        self.client_socket.close_and_send_replayable(request, req_id, params, length_pos)?;
+        self.client_socket.track_subscription(request, req_id, subscription);
+
+        Ok(())
+    }
+
+    // The streams started with this client and not cancelled or ended by an error yet, ordered
+    // by req_id. Snapshots and requests that end on their own are not included.
+    pub fn subscriptions(&self) -> Vec<(i32, Subscription)> {
+        let mut subscriptions: Vec<(i32, Subscription)> = self.client_socket.subscriptions.lock().unwrap().iter()
+            .map(|((_, req_id), subscription)| (*req_id, *subscription))
+            .collect();
+        subscriptions.sort_by_key(|(req_id, _)| *req_id);
+        subscriptions
+    }
+
+    // Sends the cancel messages matching the streams that were started with req_id.
+    pub fn cancel(&mut self, req_id: i32) {
+        let subscriptions: Vec<Subscription> = self.subscriptions().into_iter()
+            .filter(|(id, _)| *id == req_id)
+            .map(|(_, subscription)| subscription)
+            .collect();
+
+        if subscriptions.is_empty() {
+            self.report_error(req_id, client_errors::FAIL_GENERIC, "No active subscription for this request id.");
+        }
+
+        for subscription in subscriptions {
+            match subscription {
+                Subscription::MarketData => self.cancel_market_data(req_id),
+                Subscription::ScannerSubscription => self.cancel_scanner_subscription(req_id),
+                Subscription::AccountSummary => self.cancel_account_summary(req_id),
+                Subscription::HistoricalData => self.cancel_historical_data(req_id),
+                Subscription::RealTimeBars => self.cancel_real_time_bars(req_id),
+                Subscription::TickByTick => self.cancel_tick_subscription(req_id),
+                Subscription::PnL => self.cancel_pnl(req_id),
+                Subscription::PnLSingle => self.cancel_pnl_single(req_id),
+                Subscription::MarketDepth { is_smart_depth } => self.cancel_market_depth(req_id, is_smart_depth),
+            }
+        }
+    }
+
     pub fn stop(&self) {
         let msg = IncomingMessagesEnum::Stop;
         self.event_sender.send(msg).unwrap_or_else(|err| {
@@ -1215,26 +2121,26 @@ impl IbClient {
     }
 
     fn verify_order(&self, order: &Order, id: i32, is_bag_order: bool) -> bool {
-        if self.client_socket.server_version < min_server_version::SCALE_ORDERS {
+        if self.client_socket.server_version() < min_server_version::SCALE_ORDERS {
             if order.scale_init_level_size != i32::MAX || order.scale_price_increment != f64::MAX {
                 self.report_error(id, client_errors::UPDATE_TWS, "It does not support Scale orders");
                 return false;
             }
         }
 
-        if self.client_socket.server_version < min_server_version::WHAT_IF_ORDERS {
+        if self.client_socket.server_version() < min_server_version::WHAT_IF_ORDERS {
             if order.what_if {
                 self.report_error(id, client_errors::UPDATE_TWS, "it does not support what-if orders");
             }
         }
 
-        if self.client_socket.server_version < min_server_version::SCALE_ORDERS {
+        if self.client_socket.server_version() < min_server_version::SCALE_ORDERS {
             if order.scale_subs_level_size != i32::MAX {
                 self.report_error(id, client_errors::UPDATE_TWS, "it does not support subsequent Level Size for Scale Orders.");
             }
         }
 
-        if self.client_socket.server_version < min_server_version::ALGO_ORDERS
+        if self.client_socket.server_version() < min_server_version::ALGO_ORDERS
         {
             if order.algo_strategy.is_empty() == false
             {
@@ -1243,7 +2149,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::NOT_HELD
+        if self.client_socket.server_version() < min_server_version::NOT_HELD
         {
             if order.not_held
             {
@@ -1252,7 +2158,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::SSHORTX
+        if self.client_socket.server_version() < min_server_version::SSHORTX
         {
             if order.exempt_code != -1
             {
@@ -1263,7 +2169,7 @@ impl IbClient {
 
 
 
-        if self.client_socket.server_version < min_server_version::HEDGE_ORDERS
+        if self.client_socket.server_version() < min_server_version::HEDGE_ORDERS
         {
             if order.hedge_type.is_empty() == false
             {
@@ -1272,7 +2178,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::OPT_OUT_SMART_ROUTING
+        if self.client_socket.server_version() < min_server_version::OPT_OUT_SMART_ROUTING
         {
             if order.opt_out_smart_routing
             {
@@ -1281,7 +2187,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::DELTA_NEUTRAL_CONID
+        if self.client_socket.server_version() < min_server_version::DELTA_NEUTRAL_CONID
         {
             if order.delta_neutral_con_id > 0 || order.delta_neutral_settling_firm.is_empty() == false || order.delta_neutral_clearing_account.is_empty() == false || order.delta_neutral_clearing_intent.is_empty() == false
             {
@@ -1290,7 +2196,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::DELTA_NEUTRAL_OPEN_CLOSE
+        if self.client_socket.server_version() < min_server_version::DELTA_NEUTRAL_OPEN_CLOSE
         {
             if order.delta_neutral_open_close.is_empty() == false || order.delta_neutral_short_sale || order.delta_neutral_short_sale_slot > 0 || order.delta_neutral_designated_location.is_empty() == false
             {
@@ -1299,7 +2205,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::SCALE_ORDERS3
+        if self.client_socket.server_version() < min_server_version::SCALE_ORDERS3
         {
             if order.scale_price_increment > 0.0 && order.scale_price_increment != f64::MAX
             {
@@ -1317,7 +2223,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::ORDER_COMBO_LEGS_PRICE && is_bag_order
+        if self.client_socket.server_version() < min_server_version::ORDER_COMBO_LEGS_PRICE && is_bag_order
         {
             if order.order_combo_legs.len() > 0
             {
@@ -1330,7 +2236,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::TRAILING_PERCENT
+        if self.client_socket.server_version() < min_server_version::TRAILING_PERCENT
         {
             if order.trailing_percent != f64::MAX
             {
@@ -1339,14 +2245,14 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::ALGO_ID && order.algo_id.is_empty() == false
+        if self.client_socket.server_version() < min_server_version::ALGO_ID && order.algo_id.is_empty() == false
         {
             self.report_error(id, client_errors::UPDATE_TWS, "It does not support algoId parameter");
 
             return false;
         }
 
-        if self.client_socket.server_version < min_server_version::SCALE_TABLE
+        if self.client_socket.server_version() < min_server_version::SCALE_TABLE
         {
             if order.scale_table.is_empty() == false || order.active_start_time.is_empty() == false || order.active_stop_time.is_empty() == false
             {
@@ -1355,49 +2261,49 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::EXT_OPERATOR && order.ext_operator.is_empty() == false
+        if self.client_socket.server_version() < min_server_version::EXT_OPERATOR && order.ext_operator.is_empty() == false
         {
             self.report_error(id, client_errors::UPDATE_TWS, "It does not support extOperator parameter");
             return false;
         }
 
-        if self.client_socket.server_version < min_server_version::CASH_QTY && order.cash_qty != f64::MAX
+        if self.client_socket.server_version() < min_server_version::CASH_QTY && order.cash_qty != f64::MAX
         {
             self.report_error(id, client_errors::UPDATE_TWS, "It does not support cashQty parameter");
             return false;
         }
 
-        if self.client_socket.server_version < min_server_version::DECISION_MAKER && (order.mifid2decision_maker.is_empty() || order.mifid2decision_algo.is_empty())
+        if self.client_socket.server_version() < min_server_version::DECISION_MAKER && (order.mifid2decision_maker.is_empty() || order.mifid2decision_algo.is_empty())
         {
             self.report_error(id, client_errors::UPDATE_TWS, "It does not support MIFID II decision maker parameters");
             return false;
         }
 
-        if self.client_socket.server_version < min_server_version::DECISION_MAKER && (order.mifid2execution_trader.is_empty() == false || order.mifid2execution_algo.is_empty() == false)
+        if self.client_socket.server_version() < min_server_version::DECISION_MAKER && (order.mifid2execution_trader.is_empty() == false || order.mifid2execution_algo.is_empty() == false)
         {
             self.report_error(id, client_errors::UPDATE_TWS, "It does not support MIFID II execution parameters");
             return false;
         }
 
-        if self.client_socket.server_version < min_server_version::AUTO_PRICE_FOR_HEDGE && order.dont_use_auto_price_for_hedge
+        if self.client_socket.server_version() < min_server_version::AUTO_PRICE_FOR_HEDGE && order.dont_use_auto_price_for_hedge
         {
             self.report_error(id, client_errors::UPDATE_TWS, "It does not support don't use auto price for hedge parameter");
             return false;
         }
 
-        if self.client_socket.server_version < min_server_version::ORDER_CONTAINER && order.is_oms_container
+        if self.client_socket.server_version() < min_server_version::ORDER_CONTAINER && order.is_oms_container
         {
             self.report_error(id, client_errors::UPDATE_TWS, "It does not support oms container parameter.");
             return false;
         }
 
-        if self.client_socket.server_version < min_server_version::D_PEG_ORDERS && order.discretionary_up_to_limit_price
+        if self.client_socket.server_version() < min_server_version::D_PEG_ORDERS && order.discretionary_up_to_limit_price
         {
             self.report_error(id, client_errors::UPDATE_TWS, "It does not support D-Peg orders.");
             return false;
         }
 
-        if self.client_socket.server_version < min_server_version::PRICE_MGMT_ALGO
+        if self.client_socket.server_version() < min_server_version::PRICE_MGMT_ALGO
         {
             self.report_error(id, client_errors::UPDATE_TWS, "It does not support Use Price Management Algo requests.");
             return false;
@@ -1407,7 +2313,7 @@ impl IbClient {
     }
 
     fn verify_order_contract(&self, contract: &Contract, id: i32) -> bool {
-        if self.client_socket.server_version < min_server_version::SSHORT_COMBO_LEGS
+        if self.client_socket.server_version() < min_server_version::SSHORT_COMBO_LEGS
         {
             if contract.combo_legs.len() > 0
             {
@@ -1420,7 +2326,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::DELTA_NEUTRAL
+        if self.client_socket.server_version() < min_server_version::DELTA_NEUTRAL
         {
             if contract.delta_neutral_contract.is_some()
             {
@@ -1429,7 +2335,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::PLACE_ORDER_CONID
+        if self.client_socket.server_version() < min_server_version::PLACE_ORDER_CONID
         {
             if contract.con_id > 0
             {
@@ -1438,7 +2344,7 @@ impl IbClient {
             }
         }
 
-        if self.client_socket.server_version < min_server_version::SEC_ID_TYPE
+        if self.client_socket.server_version() < min_server_version::SEC_ID_TYPE
         {
             if contract.sec_id_type.is_empty() == false || contract.sec_id.is_empty() == false
             {
@@ -1446,7 +2352,7 @@ impl IbClient {
                 return false;
             }
         }
-        if self.client_socket.server_version < min_server_version::SSHORTX
+        if self.client_socket.server_version() < min_server_version::SSHORTX
         {
             if contract.combo_legs.len() > 0
             {
@@ -1458,7 +2364,7 @@ impl IbClient {
                 }
             }
         }
-        if self.client_socket.server_version < min_server_version::TRADING_CLASS
+        if self.client_socket.server_version() < min_server_version::TRADING_CLASS
         {
             if contract.trading_class.is_empty() == false
             {
@@ -1469,6 +2375,23 @@ impl IbClient {
         true
     }
 
+    fn send_open_orders_request(&mut self, message: OutgoingMessages) {
+        if self.check_connection() == false {
+            return;
+        }
+
+        const VERSION: i32 = 1;
+        let mut params_list = ApiParameters::new();
+        let length_pos = params_list.prepare_buffer(self.client_socket.use_v1000_plus);
+
+        params_list.add_int(message as i32);
+        params_list.add_int(VERSION);
+
+        self.client_socket.close_and_send(&mut params_list, length_pos).unwrap_or_else(|err| {
+            self.report_error(helper_constants::NO_VALID_ID, client_errors::FAIL_SEND_OORDER, err.to_string().as_str());
+        });
+    }
+
     fn report_error(&self, id: i32, error: (i32, &str), tail: &str) {
         let error_message = format!("{} {}", error.1, tail);
         let enum_error = IncomingMessagesEnum::Error(id, error.0, error_message);
@@ -1476,7 +2399,7 @@ impl IbClient {
     }
 
     fn check_server_version(&self, req_id: i32, required_version: i32, update_tail: &str) -> bool {
-        if self.client_socket.server_version < required_version {
+        if self.client_socket.server_version() < required_version {
             self.report_update_tws(req_id, update_tail);
         }
         return true;
@@ -1495,3 +2418,19 @@ impl IbClient {
         return true;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_requests_are_not_tracked() {
+        let mut client = IbClient::new("127.0.0.1".to_string(), 7497, 0);
+        client.is_connected = true;
+
+        client.req_pnl(3, "DU123", "");
+
+        assert!(client.subscriptions().is_empty());
+        assert!(client.event_receiver.try_iter().any(|event| matches!(event, IncomingMessagesEnum::Error(3, code, _) if code == client_errors::FAIL_SEND_REQPNL.0)));
+    }
+}
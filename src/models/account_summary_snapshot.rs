@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use crate::models::account_summary::AccountSummary;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountSummaryValue {
+    pub value: String,
+    pub currency: String,
+}
+
+impl AccountSummaryValue {
+    pub fn as_f64(&self) -> Option<f64> {
+        self.value.parse().ok()
+    }
+}
+
+// Collects the AccountSummary rows of one request. The snapshot is complete once the matching
+// AccountSummaryEnd arrived; later rows (TWS refreshes the summary every three minutes) keep
+// updating it. Ledger tags report one row per currency, so values are kept per currency.
+#[derive(Debug)]
+pub struct AccountSummarySnapshot {
+    pub req_id: i32,
+    complete: bool,
+    accounts: HashMap<String, HashMap<String, Vec<AccountSummaryValue>>>,
+}
+
+impl AccountSummarySnapshot {
+    pub fn new(req_id: i32) -> Self {
+        AccountSummarySnapshot {
+            req_id,
+            complete: false,
+            accounts: HashMap::new(),
+        }
+    }
+
+    // Rows of other requests are ignored.
+    pub fn update(&mut self, account_summary: &AccountSummary) {
+        if account_summary.request_id != self.req_id {
+            return;
+        }
+
+        let values = self.accounts.entry(account_summary.account.clone()).or_default()
+            .entry(account_summary.tag.clone()).or_default();
+        let value = AccountSummaryValue {
+            value: account_summary.value.clone(),
+            currency: account_summary.currency.clone(),
+        };
+
+        match values.iter_mut().find(|existing| existing.currency == value.currency) {
+            Some(existing) => *existing = value,
+            None => values.push(value),
+        }
+    }
+
+    pub fn end(&mut self, req_id: i32) {
+        if req_id == self.req_id {
+            self.complete = true;
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    pub fn accounts(&self) -> Vec<&str> {
+        self.accounts.keys().map(|account| account.as_str()).collect()
+    }
+
+    // The value of a tag that is reported in a single currency, e.g. "NetLiquidation".
+    pub fn value(&self, account: &str, tag: &str) -> Option<&AccountSummaryValue> {
+        self.accounts.get(account)?.get(tag)?.first()
+    }
+
+    pub fn value_in(&self, account: &str, tag: &str, currency: &str) -> Option<&AccountSummaryValue> {
+        self.accounts.get(account)?.get(tag)?.iter().find(|value| value.currency == currency)
+    }
+
+    pub fn values(&self, account: &str, tag: &str) -> &[AccountSummaryValue] {
+        self.accounts.get(account).and_then(|tags| tags.get(tag)).map_or(&[], |values| values.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(request_id: i32, account: &str, tag: &str, value: &str, currency: &str) -> AccountSummary {
+        AccountSummary {
+            request_id,
+            account: account.to_string(),
+            tag: tag.to_string(),
+            value: value.to_string(),
+            currency: currency.to_string(),
+        }
+    }
+
+    #[test]
+    fn collects_rows_until_end() {
+        let mut snapshot = AccountSummarySnapshot::new(6);
+        snapshot.update(&row(6, "DU123456", "NetLiquidation", "100000.50", "USD"));
+        snapshot.update(&row(6, "DU654321", "NetLiquidation", "2500", "USD"));
+        snapshot.update(&row(7, "DU123456", "NetLiquidation", "1", "USD"));
+        assert!(!snapshot.is_complete());
+
+        snapshot.end(7);
+        assert!(!snapshot.is_complete());
+        snapshot.end(6);
+        assert!(snapshot.is_complete());
+
+        assert_eq!(snapshot.accounts().len(), 2);
+        assert_eq!(snapshot.value("DU123456", "NetLiquidation").unwrap().as_f64(), Some(100000.5));
+    }
+
+    #[test]
+    fn ledger_rows_are_kept_per_currency() {
+        let mut snapshot = AccountSummarySnapshot::new(6);
+        snapshot.update(&row(6, "DU123456", "CashBalance", "1000", "USD"));
+        snapshot.update(&row(6, "DU123456", "CashBalance", "250", "EUR"));
+        snapshot.update(&row(6, "DU123456", "CashBalance", "300", "EUR"));
+
+        assert_eq!(snapshot.values("DU123456", "CashBalance").len(), 2);
+        assert_eq!(snapshot.value_in("DU123456", "CashBalance", "EUR").unwrap().value, "300");
+        assert!(snapshot.value_in("DU123456", "CashBalance", "GBP").is_none());
+    }
+}
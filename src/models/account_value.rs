@@ -0,0 +1,7 @@
+#[derive(Debug)]
+pub struct AccountValue {
+    pub key: String,
+    pub value: String,
+    pub currency: String,
+    pub account_name: String,
+}
@@ -1,6 +1,8 @@
 use core::fmt;
 use std::fmt::Formatter;
 use serde_derive::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 
 #[derive(Debug, Clone, Serialize)]
 pub enum BarColors {
@@ -12,7 +14,9 @@ pub enum BarColors {
 #[derive(Debug, Clone, Serialize)]
 pub struct Bar {
     pub time_str: String,
+    // UTC epoch seconds, only meaningful when has_time is set
     pub time_int: i64,
+    pub has_time: bool,
     pub open: f64,
     pub high: f64,
     pub low: f64,
@@ -28,6 +32,7 @@ impl Bar {
         Bar {
             time_str: "".to_string(),
             time_int: 0,
+            has_time: false,
             open: 0.0,
             high: 0.0,
             low: 0.0,
@@ -40,6 +45,87 @@ impl Bar {
     }
 }
 
+impl Bar {
+    // time_int is filled in by the decoder when time_str identifies the moment on its own (epoch
+    // seconds, an explicit time zone or the UTC "yyyyMMdd-HH:mm:ss" form). Bars in local time
+    // ("20220503  13:00:00", "20220503") need the time zone of the request, usually the exchange
+    // one from ContractDetails.time_zone_id. The client does not look it up: register it with
+    // IbClient::set_time_zone before the request, or set time_int afterwards with localize.
+    pub fn localize(&mut self, time_zone_id: &str) {
+        self.set_time(Bar::parse_time(self.time_str.as_str(), Some(time_zone_id)));
+    }
+
+    pub(crate) fn set_time(&mut self, date_time: Option<DateTime<Utc>>) {
+        if let Some(date_time) = date_time {
+            self.time_int = date_time.timestamp();
+            self.has_time = true;
+        }
+    }
+
+    pub fn date_time(&self) -> Option<DateTime<Utc>> {
+        if !self.has_time {
+            return None;
+        }
+
+        Some(Utc.timestamp(self.time_int, 0))
+    }
+
+    // A time zone given in time_str wins over time_zone_id.
+    pub fn parse_time(time_str: &str, time_zone_id: Option<&str>) -> Option<DateTime<Utc>> {
+        let parts: Vec<&str> = time_str.split_whitespace().collect();
+
+        match parts.as_slice() {
+            [epoch] if epoch.len() > 8 && epoch.chars().all(|c| c.is_ascii_digit()) => {
+                Some(Utc.timestamp(epoch.parse().ok()?, 0))
+            },
+            [utc] if utc.contains('-') => {
+                Some(DateTime::from_utc(NaiveDateTime::parse_from_str(utc, "%Y%m%d-%H:%M:%S").ok()?, Utc))
+            },
+            [date] => {
+                let date_time = NaiveDate::parse_from_str(date, "%Y%m%d").ok()?.and_hms(0, 0, 0);
+                from_local(date_time, parse_time_zone(time_zone_id?)?)
+            },
+            [date, time] => {
+                let date_time = NaiveDateTime::parse_from_str(format!("{} {}", date, time).as_str(), "%Y%m%d %H:%M:%S").ok()?;
+                from_local(date_time, parse_time_zone(time_zone_id?)?)
+            },
+            [date, time, zone] => {
+                let date_time = NaiveDateTime::parse_from_str(format!("{} {}", date, time).as_str(), "%Y%m%d %H:%M:%S").ok()?;
+                from_local(date_time, parse_time_zone(zone)?)
+            },
+            _ => None
+        }
+    }
+}
+
+fn from_local(date_time: NaiveDateTime, time_zone: Tz) -> Option<DateTime<Utc>> {
+    // the earlier of the two candidates when the clocks go back
+    time_zone.from_local_datetime(&date_time).earliest().map(|local| local.with_timezone(&Utc))
+}
+
+// TWS reports either IANA names ("US/Eastern") or abbreviations, sometimes followed by a
+// description ("EST (Eastern Standard Time)"). The abbreviations are mapped to a zone with
+// daylight saving rules, as used by the exchange.
+fn parse_time_zone(time_zone_id: &str) -> Option<Tz> {
+    let name = time_zone_id.split_whitespace().next()?;
+
+    let tz = match name {
+        "EST" | "EDT" => Tz::America__New_York,
+        "CST" | "CDT" => Tz::America__Chicago,
+        "CTT" => Tz::Asia__Shanghai,
+        "MST" | "MDT" => Tz::America__Denver,
+        "PST" | "PDT" => Tz::America__Los_Angeles,
+        "GB" | "BST" => Tz::Europe__London,
+        "MET" | "CET" | "CEST" => Tz::Europe__Berlin,
+        "JST" => Tz::Asia__Tokyo,
+        "HKT" => Tz::Asia__Hong_Kong,
+        "AET" | "AEST" => Tz::Australia__Sydney,
+        _ => name.parse().ok()?
+    };
+
+    Some(tz)
+}
+
 impl fmt::Display for Bar {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let mut color_str = "".to_string();
@@ -56,4 +142,80 @@ impl fmt::Display for Bar {
 
         write!(f, "time_str: {}, time_int, {}, open: {}, high: {}, low: {}, close: {}, volume: {}, wap: {}, count: {}, color: {}", self.time_str, self.time_int, self.open, self.high, self.low, self.close, self.volume, self.wap, self.count, color_str)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(time_str: &str, time_zone_id: Option<&str>) -> Option<i64> {
+        Bar::parse_time(time_str, time_zone_id).map(|date_time| date_time.timestamp())
+    }
+
+    #[test]
+    fn parses_self_describing_times() {
+        assert_eq!(parse("1651582800", None), Some(1651582800));
+        assert_eq!(parse("20220503-13:00:00", None), Some(1651582800));
+        assert_eq!(parse("20220503 09:00:00 US/Eastern", None), Some(1651582800));
+        assert_eq!(parse("20220503 09:00:00 US/Eastern", Some("Asia/Tokyo")), Some(1651582800));
+    }
+
+    #[test]
+    fn local_times_need_a_time_zone() {
+        assert_eq!(parse("20220503  09:00:00", None), None);
+        assert_eq!(parse("20220503  09:00:00", Some("US/Eastern")), Some(1651582800));
+        assert_eq!(parse("20220503  09:00:00", Some("EST (Eastern Standard Time)")), Some(1651582800));
+        assert_eq!(parse("20220105  09:00:00", Some("EST")), Some(1641391200));
+        assert_eq!(parse("20220503", Some("JST")), Some(1651503600));
+    }
+
+    #[test]
+    fn localize_sets_time_int() {
+        let mut bar = Bar::new();
+        bar.time_str = "20220503  09:00:00".to_string();
+        assert!(bar.date_time().is_none());
+
+        bar.localize("US/Eastern");
+        assert_eq!(bar.time_int, 1651582800);
+        assert_eq!(bar.date_time().unwrap().to_rfc3339(), "2022-05-03T13:00:00+00:00");
+    }
+
+    #[test]
+    fn epoch_zero_is_a_time() {
+        let mut bar = Bar::new();
+        bar.time_str = "19700101  00:00:00".to_string();
+        bar.localize("UTC");
+
+        assert_eq!(bar.date_time().map(|date_time| date_time.timestamp()), Some(0));
+    }
+
+    #[test]
+    fn time_zone_abbreviations_map_to_zones() {
+        let zones = [
+            ("EST", Tz::America__New_York), ("EDT", Tz::America__New_York),
+            ("CST", Tz::America__Chicago), ("CDT", Tz::America__Chicago),
+            ("CTT", Tz::Asia__Shanghai),
+            ("MST", Tz::America__Denver), ("MDT", Tz::America__Denver),
+            ("PST", Tz::America__Los_Angeles), ("PDT", Tz::America__Los_Angeles),
+            ("GB", Tz::Europe__London), ("BST", Tz::Europe__London),
+            ("MET", Tz::Europe__Berlin), ("CET", Tz::Europe__Berlin), ("CEST", Tz::Europe__Berlin),
+            ("JST", Tz::Asia__Tokyo),
+            ("HKT", Tz::Asia__Hong_Kong),
+            ("AET", Tz::Australia__Sydney), ("AEST", Tz::Australia__Sydney),
+        ];
+
+        for (abbreviation, zone) in zones.iter() {
+            assert_eq!(parse_time_zone(abbreviation), Some(*zone), "{}", abbreviation);
+            assert_eq!(parse_time_zone(format!("{} (description)", abbreviation).as_str()), Some(*zone), "{}", abbreviation);
+        }
+        assert_eq!(parse_time_zone("US/Eastern"), Some(Tz::US__Eastern));
+        assert_eq!(parse_time_zone("XYZ"), None);
+    }
+
+    #[test]
+    fn unknown_formats_are_ignored() {
+        assert_eq!(parse("", Some("US/Eastern")), None);
+        assert_eq!(parse("yesterday", Some("US/Eastern")), None);
+        assert_eq!(parse("20220503  09:00:00", Some("Nowhere/Special")), None);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::enums::position_type::PositionType;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ComboLeg {
     pub con_id: i32,
     pub ratio: i32,
@@ -0,0 +1,22 @@
+#[derive(Debug, Clone, Default)]
+pub struct CommissionReport {
+    pub exec_id: String,
+    pub commission: f64,
+    pub currency: String,
+    pub realized_pnl: f64,
+    pub yield_: f64,
+    pub yield_redemption_date: i32,
+}
+
+impl CommissionReport {
+    pub fn new() -> Self {
+        CommissionReport {
+            exec_id: "".to_string(),
+            commission: 0.0,
+            currency: "".to_string(),
+            realized_pnl: 0.0,
+            yield_: 0.0,
+            yield_redemption_date: 0,
+        }
+    }
+}
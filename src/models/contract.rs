@@ -1,7 +1,7 @@
 use crate::models::combo_leg::ComboLeg;
 use crate::models::delta_neutral_contract::DeltaNeutralContract;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Contract {
     pub con_id: i32,
     pub symbol: String,
@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeltaNeutralContract {
     pub con_id: i32,
     pub delta: f64,
@@ -0,0 +1,48 @@
+#[derive(Debug, Clone, Default)]
+pub struct Execution {
+    pub order_id: i32,
+    pub client_id: i32,
+    pub exec_id: String,
+    pub time: String,
+    pub acct_number: String,
+    pub exchange: String,
+    pub side: String,
+    pub shares: f64,
+    pub price: f64,
+    pub perm_id: i32,
+    pub liquidation: i32,
+    pub cum_qty: f64,
+    pub avg_price: f64,
+    pub order_ref: String,
+    pub ev_rule: String,
+    pub ev_multiplier: f64,
+    pub model_code: String,
+    pub last_liquidity: i32,
+    pub pending_price_revision: bool,
+}
+
+impl Execution {
+    pub fn new() -> Self {
+        Execution {
+            order_id: 0,
+            client_id: 0,
+            exec_id: "".to_string(),
+            time: "".to_string(),
+            acct_number: "".to_string(),
+            exchange: "".to_string(),
+            side: "".to_string(),
+            shares: 0.0,
+            price: 0.0,
+            perm_id: 0,
+            liquidation: 0,
+            cum_qty: 0.0,
+            avg_price: 0.0,
+            order_ref: "".to_string(),
+            ev_rule: "".to_string(),
+            ev_multiplier: 0.0,
+            model_code: "".to_string(),
+            last_liquidity: 0,
+            pending_price_revision: false,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+// Every field left at its default matches all executions.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionFilter {
+    pub client_id: i32,
+    pub acct_code: String,
+    // "yyyyMMdd-HH:mm:ss" (UTC) or "yyyyMMdd HH:mm:ss" followed by a time zone
+    pub time: String,
+    pub symbol: String,
+    pub sec_type: String,
+    pub exchange: String,
+    pub side: String,
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use crate::models::contract::Contract;
+use crate::models::execution::Execution;
+use crate::models::commission_report::CommissionReport;
+
+#[derive(Debug)]
+pub struct Fill {
+    pub contract: Contract,
+    pub execution: Execution,
+    pub commission_report: Option<CommissionReport>,
+}
+
+// Joins the ExecutionData and CommissionsReport events by exec_id. TWS usually sends the
+// commission report right after its execution, but the order is not guaranteed, so reports
+// that arrive first are kept until the execution shows up.
+#[derive(Debug, Default)]
+pub struct FillJournal {
+    fills: Vec<Fill>,
+    positions: HashMap<String, usize>,
+    pending_reports: HashMap<String, CommissionReport>,
+}
+
+impl FillJournal {
+    pub fn new() -> Self {
+        FillJournal::default()
+    }
+
+    // An execution that is already known (e.g. sent again in answer to req_executions) replaces
+    // the stored one and keeps its commission report.
+    pub fn add_execution(&mut self, contract: Contract, execution: Execution) {
+        let commission_report = self.pending_reports.remove(&execution.exec_id);
+
+        match self.positions.get(&execution.exec_id) {
+            Some(&position) => {
+                let fill = &mut self.fills[position];
+                fill.contract = contract;
+                fill.execution = execution;
+                if commission_report.is_some() {
+                    fill.commission_report = commission_report;
+                }
+            },
+            None => {
+                self.positions.insert(execution.exec_id.clone(), self.fills.len());
+                self.fills.push(Fill { contract, execution, commission_report });
+            }
+        }
+    }
+
+    pub fn add_commission_report(&mut self, commission_report: CommissionReport) {
+        match self.positions.get(&commission_report.exec_id) {
+            Some(&position) => self.fills[position].commission_report = Some(commission_report),
+            None => {
+                self.pending_reports.insert(commission_report.exec_id.clone(), commission_report);
+            }
+        }
+    }
+
+    pub fn fill(&self, exec_id: &str) -> Option<&Fill> {
+        self.positions.get(exec_id).map(|&position| &self.fills[position])
+    }
+
+    // Fills in the order their executions were first received.
+    pub fn fills(&self) -> &[Fill] {
+        &self.fills
+    }
+
+    pub fn fills_without_commission(&self) -> Vec<&Fill> {
+        self.fills.iter().filter(|fill| fill.commission_report.is_none()).collect()
+    }
+
+    pub fn unmatched_commission_reports(&self) -> Vec<&CommissionReport> {
+        self.pending_reports.values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution(exec_id: &str, shares: f64) -> Execution {
+        let mut execution = Execution::new();
+        execution.exec_id = exec_id.to_string();
+        execution.shares = shares;
+        execution
+    }
+
+    fn commission_report(exec_id: &str, commission: f64) -> CommissionReport {
+        let mut commission_report = CommissionReport::new();
+        commission_report.exec_id = exec_id.to_string();
+        commission_report.commission = commission;
+        commission_report
+    }
+
+    #[test]
+    fn joins_commission_report_received_after_execution() {
+        let mut journal = FillJournal::new();
+        journal.add_execution(Contract::new(), execution("0001.01", 100.0));
+        journal.add_execution(Contract::new(), execution("0002.01", 50.0));
+        journal.add_commission_report(commission_report("0001.01", 1.25));
+
+        assert_eq!(journal.fill("0001.01").unwrap().commission_report.as_ref().unwrap().commission, 1.25);
+        assert_eq!(journal.fills_without_commission().len(), 1);
+        assert!(journal.unmatched_commission_reports().is_empty());
+    }
+
+    #[test]
+    fn joins_commission_report_received_before_execution() {
+        let mut journal = FillJournal::new();
+        journal.add_commission_report(commission_report("0001.01", 1.25));
+        assert_eq!(journal.unmatched_commission_reports().len(), 1);
+
+        journal.add_execution(Contract::new(), execution("0001.01", 100.0));
+
+        assert!(journal.fill("0001.01").unwrap().commission_report.is_some());
+        assert!(journal.unmatched_commission_reports().is_empty());
+    }
+
+    #[test]
+    fn repeated_execution_keeps_its_commission() {
+        let mut journal = FillJournal::new();
+        journal.add_execution(Contract::new(), execution("0001.01", 100.0));
+        journal.add_commission_report(commission_report("0001.01", 1.25));
+        journal.add_execution(Contract::new(), execution("0001.01", 100.0));
+
+        assert_eq!(journal.fills().len(), 1);
+        assert!(journal.fill("0001.01").unwrap().commission_report.is_some());
+    }
+}
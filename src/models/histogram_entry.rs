@@ -0,0 +1,5 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramEntry {
+    pub price: f64,
+    pub size: i64,
+}
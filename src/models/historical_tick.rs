@@ -0,0 +1,7 @@
+// A midpoint tick returned by req_historical_ticks, size is always 0.
+#[derive(Debug, Clone)]
+pub struct HistoricalTick {
+    pub time: i64,
+    pub price: f64,
+    pub size: i64,
+}
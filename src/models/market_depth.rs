@@ -0,0 +1,14 @@
+use crate::enums::market_depth_operation::MarketDepthOperation;
+use crate::enums::market_depth_side::MarketDepthSide;
+
+#[derive(Debug, Clone)]
+pub struct MarketDepth {
+    pub req_id: i32,
+    pub position: i32,
+    pub market_maker: String,
+    pub operation: MarketDepthOperation,
+    pub side: MarketDepthSide,
+    pub price: f64,
+    pub size: f64,
+    pub is_smart_depth: bool,
+}
@@ -15,3 +15,25 @@ pub mod order_data_item;
 pub mod order_status_message;
 pub mod bar;
 pub mod tick_last;
+pub mod tick_attrib;
+pub mod tick_attrib_last;
+pub mod tick_attrib_bid_ask;
+pub mod tick_bid_ask;
+pub mod tick_mid_point;
+pub mod market_depth;
+pub mod order_book;
+pub mod execution;
+pub mod execution_filter;
+pub mod commission_report;
+pub mod fill_journal;
+pub mod position;
+pub mod portfolio_item;
+pub mod account_value;
+pub mod account_summary_snapshot;
+pub mod option_chain;
+pub mod option_computation;
+pub mod historical_tick;
+pub mod histogram_entry;
+pub mod pnl;
+pub mod pnl_single;
+pub mod order_bound;
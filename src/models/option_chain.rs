@@ -0,0 +1,90 @@
+use std::ops::RangeInclusive;
+use crate::models::contract::Contract;
+
+// The expirations and strikes available for an underlying on one exchange and trading class,
+// as reported by req_sec_def_opt_params.
+#[derive(Debug, Clone)]
+pub struct OptionChain {
+    pub exchange: String,
+    pub underlying_con_id: i32,
+    pub trading_class: String,
+    pub multiplier: String,
+    // yyyyMMdd
+    pub expirations: Vec<String>,
+    pub strikes: Vec<f64>,
+}
+
+impl OptionChain {
+    // Builds the call and put contracts of every expiration and strike in the given ranges,
+    // ordered by expiration, then strike, calls first. sec_type is "OPT" for stock and index
+    // options and "FOP" for options on futures.
+    pub fn contracts(&self, symbol: &str, sec_type: &str, currency: &str, expirations: RangeInclusive<&str>, strikes: RangeInclusive<f64>) -> Vec<Contract> {
+        let mut selected_expirations: Vec<&String> = self.expirations.iter()
+            .filter(|expiration| expirations.contains(&expiration.as_str()))
+            .collect();
+        selected_expirations.sort();
+
+        let mut selected_strikes: Vec<f64> = self.strikes.iter().cloned()
+            .filter(|strike| strikes.contains(strike))
+            .collect();
+        selected_strikes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut contracts = vec![];
+        for expiration in selected_expirations {
+            for strike in selected_strikes.iter() {
+                for right in ["C", "P"].iter() {
+                    let mut contract = Contract::new();
+                    contract.symbol = symbol.to_string();
+                    contract.sec_type = sec_type.to_string();
+                    contract.currency = currency.to_string();
+                    contract.exchange = self.exchange.clone();
+                    contract.trading_class = self.trading_class.clone();
+                    contract.multiplier = self.multiplier.clone();
+                    contract.last_trade_date_or_contract_month = expiration.clone();
+                    contract.strike = *strike;
+                    contract.right = right.to_string();
+                    contracts.push(contract);
+                }
+            }
+        }
+
+        contracts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain() -> OptionChain {
+        OptionChain {
+            exchange: "SMART".to_string(),
+            underlying_con_id: 265598,
+            trading_class: "AAPL".to_string(),
+            multiplier: "100".to_string(),
+            expirations: vec!["20220617".to_string(), "20220520".to_string(), "20230120".to_string()],
+            strikes: vec![190.0, 180.0, 185.0, 200.0, 170.0],
+        }
+    }
+
+    #[test]
+    fn expands_contracts_in_range() {
+        let contracts = chain().contracts("AAPL", "OPT", "USD", "20220501"..="20221231", 180.0..=190.0);
+
+        assert_eq!(contracts.len(), 2 * 3 * 2);
+
+        let first = &contracts[0];
+        assert_eq!((first.last_trade_date_or_contract_month.as_str(), first.strike, first.right.as_str()), ("20220520", 180.0, "C"));
+        assert_eq!((first.symbol.as_str(), first.sec_type.as_str(), first.exchange.as_str()), ("AAPL", "OPT", "SMART"));
+        assert_eq!((first.trading_class.as_str(), first.multiplier.as_str(), first.currency.as_str()), ("AAPL", "100", "USD"));
+
+        let last = contracts.last().unwrap();
+        assert_eq!((last.last_trade_date_or_contract_month.as_str(), last.strike, last.right.as_str()), ("20220617", 190.0, "P"));
+    }
+
+    #[test]
+    fn empty_ranges_give_no_contracts() {
+        assert!(chain().contracts("AAPL", "OPT", "USD", "20240101"..="20241231", 0.0..=1000.0).is_empty());
+        assert!(chain().contracts("AAPL", "OPT", "USD", "20220101"..="20241231", 300.0..=400.0).is_empty());
+    }
+}
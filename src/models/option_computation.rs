@@ -0,0 +1,15 @@
+use crate::enums::volatility_basis::VolatilityBasis;
+
+// Fields IB reports as not computed are None. tick_attrib is only sent by newer servers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionComputation {
+    pub tick_attrib: Option<VolatilityBasis>,
+    pub implied_vol: Option<f64>,
+    pub delta: Option<f64>,
+    pub opt_price: Option<f64>,
+    pub pv_dividend: Option<f64>,
+    pub gamma: Option<f64>,
+    pub vega: Option<f64>,
+    pub theta: Option<f64>,
+    pub und_price: Option<f64>,
+}
@@ -1,8 +1,11 @@
+use std::io;
 use crate::models::soft_dollar_tier::SoftDollarTier;
 use crate::enums::origin::Origin;
 use crate::models::tag_value::TagValue;
 use crate::models::order_combo_leg::OrderComboLeg;
 use crate::traits::order_condition::OrderCondition;
+use crate::enums::oca_type::OcaType;
+use crate::enums::adjusted_stop::AdjustedStop;
 
 pub struct Order {
     pub soft_dollar_tier: SoftDollarTier,
@@ -315,4 +318,221 @@ impl Order {
             use_price_mgmt_algo: false
         }
     }
-}
\ No newline at end of file
+
+    pub fn market(action: &str, quantity: f64) -> Order {
+        let mut order = Order::new();
+        order.action = action.to_string();
+        order.order_type = "MKT".to_string();
+        order.total_quantity = quantity;
+        order
+    }
+
+    pub fn limit(action: &str, quantity: f64, limit_price: f64) -> Order {
+        let mut order = Order::market(action, quantity);
+        order.order_type = "LMT".to_string();
+        order.lmt_price = limit_price;
+        order
+    }
+
+    pub fn stop(action: &str, quantity: f64, stop_price: f64) -> Order {
+        let mut order = Order::market(action, quantity);
+        order.order_type = "STP".to_string();
+        order.aux_price = stop_price;
+        order
+    }
+
+    // An entry limit order with a take profit and a stop loss that only become active once the
+    // entry fills. The orders get the ids parent_order_id, parent_order_id + 1 and
+    // parent_order_id + 2 and must be placed in that order: only the last one is transmitted,
+    // which releases the whole bracket. OrderManager::place_group renumbers them and keeps the
+    // links. Fails on an action other than BUY, SELL or SSHORT, in any case.
+    pub fn bracket(parent_order_id: i32, action: &str, quantity: f64, limit_price: f64, take_profit_limit_price: f64, stop_loss_price: f64) -> io::Result<Vec<Order>> {
+        let (entry_action, exit_action) = entry_and_exit_actions(action)?;
+
+        let mut parent = Order::limit(entry_action, quantity, limit_price);
+        parent.order_id = parent_order_id;
+        parent.transmit = false;
+
+        let mut take_profit = Order::limit(exit_action, quantity, take_profit_limit_price);
+        take_profit.order_id = parent_order_id + 1;
+        take_profit.parent_id = parent_order_id;
+        take_profit.transmit = false;
+
+        let mut stop_loss = Order::stop(exit_action, quantity, stop_loss_price);
+        stop_loss.order_id = parent_order_id + 2;
+        stop_loss.parent_id = parent_order_id;
+        stop_loss.transmit = true;
+
+        Ok(vec![parent, take_profit, stop_loss])
+    }
+
+    // Puts the orders in one OCA group, the group name must be unique among the working orders.
+    pub fn one_cancels_all(oca_group: &str, oca_type: OcaType, mut orders: Vec<Order>) -> Vec<Order> {
+        for order in orders.iter_mut() {
+            order.oca_group = oca_group.to_string();
+            order.oca_type = oca_type as i32;
+        }
+        orders
+    }
+
+    // trail_stop_price is the initial stop, leave it at f64::MAX to start from the market price.
+    pub fn trailing_stop(action: &str, quantity: f64, trailing_amount: f64, trail_stop_price: f64) -> Order {
+        let mut order = Order::market(action, quantity);
+        order.order_type = "TRAIL".to_string();
+        order.aux_price = trailing_amount;
+        order.trail_stop_price = trail_stop_price;
+        order
+    }
+
+    pub fn trailing_stop_percent(action: &str, quantity: f64, trailing_percent: f64, trail_stop_price: f64) -> Order {
+        let mut order = Order::market(action, quantity);
+        order.order_type = "TRAIL".to_string();
+        order.trailing_percent = trailing_percent;
+        order.trail_stop_price = trail_stop_price;
+        order
+    }
+
+    // Once the stop is hit, a limit order is sent at the stop price plus lmt_price_offset.
+    pub fn trailing_stop_limit(action: &str, quantity: f64, trailing_amount: f64, trail_stop_price: f64, lmt_price_offset: f64) -> Order {
+        let mut order = Order::trailing_stop(action, quantity, trailing_amount, trail_stop_price);
+        order.order_type = "TRAIL LIMIT".to_string();
+        order.lmt_price_offset = lmt_price_offset;
+        order
+    }
+
+    // A stop attached to parent that is changed into the adjusted order once trigger_price is
+    // reached, e.g. to move the stop to break even. The parent is no longer transmitted on its
+    // own: place it first, then the stop with the given order id. The parent needs its order id
+    // already, a stop with parent_id 0 would not be attached to anything.
+    pub fn adjustable_stop(parent: &mut Order, order_id: i32, stop_price: f64, trigger_price: f64, adjusted: AdjustedStop) -> io::Result<Order> {
+        if parent.order_id == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "The parent of an adjustable stop needs an order id"));
+        }
+
+        let (entry_action, exit_action) = entry_and_exit_actions(parent.action.as_str())?;
+        parent.action = entry_action.to_string();
+        parent.transmit = false;
+
+        let mut order = Order::stop(exit_action, parent.total_quantity, stop_price);
+        order.order_id = order_id;
+        order.parent_id = parent.order_id;
+        order.trigger_price = trigger_price;
+
+        match adjusted {
+            AdjustedStop::Stop { stop_price } => {
+                order.adjusted_order_type = "STP".to_string();
+                order.adjusted_stop_price = stop_price;
+            },
+            AdjustedStop::StopLimit { stop_price, limit_price } => {
+                order.adjusted_order_type = "STP LMT".to_string();
+                order.adjusted_stop_price = stop_price;
+                order.adjusted_stop_limit_price = limit_price;
+            },
+            AdjustedStop::TrailingAmount { stop_price, trailing_amount } => {
+                order.adjusted_order_type = "TRAIL".to_string();
+                order.adjusted_stop_price = stop_price;
+                order.adjusted_trailing_amount = trailing_amount;
+                order.adjustable_trailing_unit = 0;
+            },
+            AdjustedStop::TrailingPercent { stop_price, trailing_percent } => {
+                order.adjusted_order_type = "TRAIL".to_string();
+                order.adjusted_stop_price = stop_price;
+                order.adjusted_trailing_amount = trailing_percent;
+                order.adjustable_trailing_unit = 1;
+            },
+        }
+
+        Ok(order)
+    }
+}
+
+// The action in upper case and the one closing a position opened with it, used for the legs
+// of bracket and adjustable_stop.
+fn entry_and_exit_actions(action: &str) -> io::Result<(&'static str, &'static str)> {
+    match action.to_uppercase().as_str() {
+        "BUY" => Ok(("BUY", "SELL")),
+        "SELL" => Ok(("SELL", "BUY")),
+        "SSHORT" => Ok(("SSHORT", "BUY")),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Unknown order action {:?}, expected BUY, SELL or SSHORT", action)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bracket_orders_are_linked_to_the_parent() {
+        let orders = Order::bracket(10, "BUY", 100.0, 185.0, 195.0, 180.0).unwrap();
+
+        let ids: Vec<(i32, i32, bool)> = orders.iter().map(|order| (order.order_id, order.parent_id, order.transmit)).collect();
+        assert_eq!(ids, vec![(10, 0, false), (11, 10, false), (12, 10, true)]);
+
+        let legs: Vec<(&str, &str, f64)> = orders.iter().map(|order| (order.action.as_str(), order.order_type.as_str(), order.total_quantity)).collect();
+        assert_eq!(legs, vec![("BUY", "LMT", 100.0), ("SELL", "LMT", 100.0), ("SELL", "STP", 100.0)]);
+        assert_eq!((orders[0].lmt_price, orders[1].lmt_price, orders[2].aux_price), (185.0, 195.0, 180.0));
+    }
+
+    #[test]
+    fn oca_group_is_set_on_every_order() {
+        let orders = Order::one_cancels_all("exit-aapl", OcaType::ReduceWithBlock, vec![
+            Order::limit("SELL", 50.0, 195.0),
+            Order::limit("SELL", 50.0, 200.0),
+        ]);
+
+        assert!(orders.iter().all(|order| order.oca_group == "exit-aapl" && order.oca_type == 2));
+    }
+
+    #[test]
+    fn trailing_stop_limit_keeps_the_trailing_amount() {
+        let order = Order::trailing_stop_limit("SELL", 100.0, 2.5, 182.5, 0.1);
+
+        assert_eq!(order.order_type, "TRAIL LIMIT");
+        assert_eq!((order.aux_price, order.trail_stop_price, order.lmt_price_offset), (2.5, 182.5, 0.1));
+        assert_eq!(Order::trailing_stop_percent("SELL", 100.0, 1.5, f64::MAX).trailing_percent, 1.5);
+    }
+
+    #[test]
+    fn adjustable_stop_holds_back_the_parent() {
+        let mut parent = Order::limit("BUY", 100.0, 185.0);
+        parent.order_id = 20;
+
+        let stop = Order::adjustable_stop(&mut parent, 21, 180.0, 190.0, AdjustedStop::TrailingPercent { stop_price: 185.0, trailing_percent: 1.0 }).unwrap();
+
+        assert!(!parent.transmit);
+        assert_eq!((stop.order_id, stop.parent_id, stop.action.as_str(), stop.aux_price), (21, 20, "SELL", 180.0));
+        assert_eq!((stop.trigger_price, stop.adjusted_order_type.as_str(), stop.adjusted_stop_price), (190.0, "TRAIL", 185.0));
+        assert_eq!((stop.adjusted_trailing_amount, stop.adjustable_trailing_unit), (1.0, 1));
+    }
+
+    #[test]
+    fn exit_legs_take_the_opposite_side() {
+        assert_eq!(entry_and_exit_actions("buy").unwrap(), ("BUY", "SELL"));
+        assert_eq!(entry_and_exit_actions("Sell").unwrap(), ("SELL", "BUY"));
+        assert_eq!(entry_and_exit_actions("SSHORT").unwrap(), ("SSHORT", "BUY"));
+
+        let orders = Order::bracket(10, "buy", 100.0, 185.0, 195.0, 180.0).unwrap();
+        assert_eq!(orders.iter().map(|order| order.action.as_str()).collect::<Vec<_>>(), vec!["BUY", "SELL", "SELL"]);
+    }
+
+    #[test]
+    fn unknown_actions_are_refused() {
+        let error = Order::bracket(10, "HOLD", 100.0, 185.0, 195.0, 180.0).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(error.to_string().contains("HOLD"));
+
+        let mut parent = Order::limit("HOLD", 100.0, 185.0);
+        parent.order_id = 20;
+        assert!(Order::adjustable_stop(&mut parent, 21, 180.0, 190.0, AdjustedStop::Stop { stop_price: 185.0 }).is_err());
+        assert!(parent.transmit);
+    }
+
+    #[test]
+    fn adjustable_stop_needs_a_placed_parent() {
+        let mut parent = Order::limit("BUY", 100.0, 185.0);
+
+        let error = Order::adjustable_stop(&mut parent, 21, 180.0, 190.0, AdjustedStop::Stop { stop_price: 185.0 }).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(parent.transmit);
+    }
+}
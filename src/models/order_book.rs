@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use crate::enums::market_depth_operation::MarketDepthOperation;
+use crate::enums::market_depth_side::MarketDepthSide;
+use crate::models::market_depth::MarketDepth;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepthLevel {
+    pub market_maker: String,
+    pub price: f64,
+    pub size: f64,
+}
+
+#[derive(Debug, Default)]
+struct BookSides {
+    bids: Vec<DepthLevel>,
+    asks: Vec<DepthLevel>,
+}
+
+// Keeps the rows of every market depth subscription up to date from the MarketDepth and
+// MarketDepthL2 events. Rows are ordered by position, best price first, as sent by TWS.
+#[derive(Debug, Default)]
+pub struct OrderBook {
+    books: HashMap<i32, BookSides>,
+}
+
+impl OrderBook {
+    pub fn new() -> Self {
+        OrderBook::default()
+    }
+
+    pub fn apply(&mut self, market_depth: &MarketDepth) {
+        let book = self.books.entry(market_depth.req_id).or_default();
+        let rows = match market_depth.side {
+            MarketDepthSide::Bid => &mut book.bids,
+            MarketDepthSide::Ask => &mut book.asks,
+            MarketDepthSide::Unknown => return,
+        };
+
+        if market_depth.position < 0 {
+            return;
+        }
+
+        let position = market_depth.position as usize;
+        let level = DepthLevel {
+            market_maker: market_depth.market_maker.clone(),
+            price: market_depth.price,
+            size: market_depth.size,
+        };
+
+        match market_depth.operation {
+            MarketDepthOperation::Insert => {
+                rows.insert(std::cmp::min(position, rows.len()), level);
+            },
+            MarketDepthOperation::Update => {
+                if position < rows.len() {
+                    rows[position] = level;
+                } else {
+                    rows.push(level);
+                }
+            },
+            MarketDepthOperation::Delete => {
+                if position < rows.len() {
+                    rows.remove(position);
+                }
+            },
+            MarketDepthOperation::Unknown => {}
+        }
+    }
+
+    pub fn top_bids(&self, req_id: i32, depth: usize) -> &[DepthLevel] {
+        self.books.get(&req_id).map_or(&[], |book| &book.bids[..std::cmp::min(depth, book.bids.len())])
+    }
+
+    pub fn top_asks(&self, req_id: i32, depth: usize) -> &[DepthLevel] {
+        self.books.get(&req_id).map_or(&[], |book| &book.asks[..std::cmp::min(depth, book.asks.len())])
+    }
+
+    pub fn bids_by_market_maker(&self, req_id: i32, market_maker: &str) -> Vec<&DepthLevel> {
+        self.books.get(&req_id).map_or(vec![], |book| book.bids.iter().filter(|level| level.market_maker == market_maker).collect())
+    }
+
+    pub fn asks_by_market_maker(&self, req_id: i32, market_maker: &str) -> Vec<&DepthLevel> {
+        self.books.get(&req_id).map_or(vec![], |book| book.asks.iter().filter(|level| level.market_maker == market_maker).collect())
+    }
+
+    // TWS resends the whole book after a resubscription, so stale rows have to be dropped when
+    // the connection is lost or the subscription is cancelled.
+    pub fn remove(&mut self, req_id: i32) {
+        self.books.remove(&req_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.books.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depth(position: i32, market_maker: &str, operation: MarketDepthOperation, side: MarketDepthSide, price: f64, size: f64) -> MarketDepth {
+        MarketDepth {
+            req_id: 7,
+            position,
+            market_maker: market_maker.to_string(),
+            operation,
+            side,
+            price,
+            size,
+            is_smart_depth: false,
+        }
+    }
+
+    fn prices(levels: &[DepthLevel]) -> Vec<f64> {
+        levels.iter().map(|level| level.price).collect()
+    }
+
+    #[test]
+    fn insert_shifts_rows_down() {
+        let mut book = OrderBook::new();
+        book.apply(&depth(0, "", MarketDepthOperation::Insert, MarketDepthSide::Bid, 10.0, 1.0));
+        book.apply(&depth(1, "", MarketDepthOperation::Insert, MarketDepthSide::Bid, 9.0, 1.0));
+        book.apply(&depth(0, "", MarketDepthOperation::Insert, MarketDepthSide::Bid, 11.0, 1.0));
+
+        assert_eq!(prices(book.top_bids(7, 10)), vec![11.0, 10.0, 9.0]);
+        assert!(book.top_asks(7, 10).is_empty());
+    }
+
+    #[test]
+    fn update_and_delete_rows() {
+        let mut book = OrderBook::new();
+        for (position, price) in [(0, 10.0), (1, 10.5), (2, 11.0)].iter() {
+            book.apply(&depth(*position, "", MarketDepthOperation::Insert, MarketDepthSide::Ask, *price, 1.0));
+        }
+
+        book.apply(&depth(1, "", MarketDepthOperation::Update, MarketDepthSide::Ask, 10.25, 5.0));
+        assert_eq!(book.top_asks(7, 10)[1], DepthLevel { market_maker: String::new(), price: 10.25, size: 5.0 });
+
+        book.apply(&depth(0, "", MarketDepthOperation::Delete, MarketDepthSide::Ask, 0.0, 0.0));
+        assert_eq!(prices(book.top_asks(7, 10)), vec![10.25, 11.0]);
+        assert_eq!(prices(book.top_asks(7, 1)), vec![10.25]);
+    }
+
+    #[test]
+    fn rows_by_market_maker() {
+        let mut book = OrderBook::new();
+        book.apply(&depth(0, "ARCA", MarketDepthOperation::Insert, MarketDepthSide::Bid, 10.0, 1.0));
+        book.apply(&depth(1, "NSDQ", MarketDepthOperation::Insert, MarketDepthSide::Bid, 9.5, 2.0));
+        book.apply(&depth(2, "ARCA", MarketDepthOperation::Insert, MarketDepthSide::Bid, 9.0, 3.0));
+
+        assert_eq!(book.bids_by_market_maker(7, "ARCA").iter().map(|level| level.size).collect::<Vec<_>>(), vec![1.0, 3.0]);
+        assert!(book.asks_by_market_maker(7, "ARCA").is_empty());
+    }
+
+    #[test]
+    fn remove_drops_the_subscription() {
+        let mut book = OrderBook::new();
+        book.apply(&depth(0, "", MarketDepthOperation::Insert, MarketDepthSide::Bid, 10.0, 1.0));
+        book.remove(7);
+
+        assert!(book.top_bids(7, 10).is_empty());
+    }
+}
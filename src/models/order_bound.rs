@@ -0,0 +1,8 @@
+// Ties the perm id of an order placed in TWS to the api order id it was given, once
+// req_auto_open_orders bound it to the client.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderBound {
+    pub perm_id: i64,
+    pub api_client_id: i32,
+    pub api_order_id: i32,
+}
@@ -0,0 +1,8 @@
+// Daily, unrealized and realized PnL of an account or model, from req_pnl.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PnL {
+    pub req_id: i32,
+    pub daily_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub realized_pnl: f64,
+}
@@ -0,0 +1,11 @@
+// PnL of a single position, from req_pnl_single. TWS sends f64::MAX for the values it cannot
+// compute yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PnLSingle {
+    pub req_id: i32,
+    pub position: f64,
+    pub daily_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub realized_pnl: f64,
+    pub value: f64,
+}
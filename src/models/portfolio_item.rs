@@ -0,0 +1,13 @@
+use crate::models::contract::Contract;
+
+#[derive(Debug)]
+pub struct PortfolioItem {
+    pub contract: Contract,
+    pub position: f64,
+    pub market_price: f64,
+    pub market_value: f64,
+    pub average_cost: f64,
+    pub unrealized_pnl: f64,
+    pub realized_pnl: f64,
+    pub account_name: String,
+}
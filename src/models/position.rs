@@ -0,0 +1,9 @@
+use crate::models::contract::Contract;
+
+#[derive(Debug)]
+pub struct Position {
+    pub account: String,
+    pub contract: Contract,
+    pub position: f64,
+    pub avg_cost: f64,
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Clone, Default)]
+pub struct TickAttrib {
+    pub can_auto_execute: bool,
+    pub past_limit: bool,
+    pub pre_open: bool,
+}
@@ -0,0 +1,14 @@
+#[derive(Debug, Clone, Default)]
+pub struct TickAttribBidAsk {
+    pub bid_past_low: bool,
+    pub ask_past_high: bool,
+}
+
+impl From<i32> for TickAttribBidAsk {
+    fn from(mask: i32) -> Self {
+        TickAttribBidAsk {
+            bid_past_low: mask & 1 != 0,
+            ask_past_high: mask & 2 != 0,
+        }
+    }
+}
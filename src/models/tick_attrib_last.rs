@@ -0,0 +1,14 @@
+#[derive(Debug, Clone, Default)]
+pub struct TickAttribLast {
+    pub past_limit: bool,
+    pub unreported: bool,
+}
+
+impl From<i32> for TickAttribLast {
+    fn from(mask: i32) -> Self {
+        TickAttribLast {
+            past_limit: mask & 1 != 0,
+            unreported: mask & 2 != 0,
+        }
+    }
+}
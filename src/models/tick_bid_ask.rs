@@ -0,0 +1,11 @@
+use crate::models::tick_attrib_bid_ask::TickAttribBidAsk;
+
+#[derive(Debug, Clone)]
+pub struct TickBidAsk {
+    pub time: i64,
+    pub bid_price: f64,
+    pub ask_price: f64,
+    pub bid_size: i32,
+    pub ask_size: i32,
+    pub tick_attrib_bid_ask: TickAttribBidAsk
+}
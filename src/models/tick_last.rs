@@ -1,9 +1,12 @@
+use crate::models::tick_attrib_last::TickAttribLast;
+
 #[derive(Debug, Clone)]
 pub struct TickLast {
     pub time: i64,
     pub price: f64,
     pub size: i32,
     pub mask: i32,
+    pub tick_attrib_last: TickAttribLast,
     pub exchange: String,
     pub special_conditions: String
 }
@@ -15,6 +18,7 @@ impl TickLast {
             price: 0.0,
             size: 0,
             mask: 0,
+            tick_attrib_last: TickAttribLast::default(),
             exchange: "".to_string(),
             special_conditions: "".to_string()
         }
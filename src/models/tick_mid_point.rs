@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub struct TickMidPoint {
+    pub time: i64,
+    pub mid_point: f64
+}
@@ -1,4 +1,4 @@
-use crate::decoder::{decode_i32, decode_string, decode_f64, decode_f64_show_unset, decode_bool, decode_i32_show_unset};
+use crate::decoder::{decode_i32, decode_i64, decode_string, decode_f64, decode_f64_show_unset, decode_bool, decode_i32_show_unset};
 use crate::models::contract::Contract;
 use crate::models::order::Order;
 use crate::models::order_state::OrderState;
@@ -736,55 +736,55 @@ impl OrderDecoder<'_> {
         Ok(())
     }
 
-    // pub fn read_auto_cancel_date(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order.auto_cancel_date = decode_string(&mut self.fields_iter)?;
-    //     Ok(())
-    // }
-    //
-    // pub fn read_filled_quantity(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order.filled_quantity = decode_f64_show_unset(&mut self.fields_iter)?;
-    //     Ok(())
-    // }
-    //
-    // pub fn read_ref_futures_con_id(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order.ref_futures_con_id = decode_i32(&mut self.fields_iter)?;
-    //     Ok(())
-    // }
-    //
-    // pub fn read_auto_cancel_parent(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order.auto_cancel_parent = decode_bool(&mut self.fields_iter)?;
-    //     Ok(())
-    // }
-    //
-    // pub fn read_shareholder(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order.shareholder = decode_string(&mut self.fields_iter)?;
-    //     Ok(())
-    // }
-
-    // pub fn read_imbalance_only(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order.imbalance_only = decode_bool(&mut self.fields_iter)?;
-    //     Ok(())
-    // }
-    //
-    // pub fn read_route_marketable_to_bbo(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order.route_marketable_to_bbo = decode_bool(&mut self.fields_iter)?;
-    //     Ok(())
-    // }
-    //
-    // pub fn read_parent_perm_id(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order.parent_perm_id = decode_i32(&mut self.fields_iter)? as i64;
-    //     Ok(())
-    // }
-    //
-    // pub fn read_completed_time(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order_state.completed_time = decode_string(&mut self.fields_iter)?;
-    //     Ok(())
-    // }
-    //
-    // pub fn read_completed_status(&mut self) -> Result<(), Box<dyn Error>> {
-    //     self.order_state.completed_status = decode_string(&mut self.fields_iter)?;
-    //     Ok(())
-    // }
+    pub fn read_auto_cancel_date(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order.auto_cancel_date = decode_string(&mut self.fields_iter)?;
+        Ok(())
+    }
+
+    pub fn read_filled_quantity(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order.filled_quantity = decode_f64_show_unset(&mut self.fields_iter)?;
+        Ok(())
+    }
+
+    pub fn read_ref_futures_con_id(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order.ref_futures_con_id = decode_i32(&mut self.fields_iter)?;
+        Ok(())
+    }
+
+    pub fn read_auto_cancel_parent(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order.auto_cancel_parent = decode_bool(&mut self.fields_iter)?;
+        Ok(())
+    }
+
+    pub fn read_shareholder(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order.shareholder = decode_string(&mut self.fields_iter)?;
+        Ok(())
+    }
+
+    pub fn read_imbalance_only(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order.imbalance_only = decode_bool(&mut self.fields_iter)?;
+        Ok(())
+    }
+
+    pub fn read_route_marketable_to_bbo(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order.route_marketable_to_bbo = decode_bool(&mut self.fields_iter)?;
+        Ok(())
+    }
+
+    pub fn read_parent_perm_id(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order.parent_perm_id = decode_i64(&mut self.fields_iter)?;
+        Ok(())
+    }
+
+    pub fn read_completed_time(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order_state.completed_time = decode_string(&mut self.fields_iter)?;
+        Ok(())
+    }
+
+    pub fn read_completed_status(&mut self) -> Result<(), Box<dyn Error>> {
+        self.order_state.completed_status = decode_string(&mut self.fields_iter)?;
+        Ok(())
+    }
 
     pub fn read_use_price_mgmt_algo(&mut self) -> Result<(), Box<dyn Error>> {
         if self.server_version >= min_server_version::PRICE_MGMT_ALGO {
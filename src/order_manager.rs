@@ -0,0 +1,466 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::io;
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use crate::IbClient;
+use crate::enums::incoming_message_enum::IncomingMessagesEnum;
+use crate::enums::order_lifecycle_state::OrderLifecycleState;
+use crate::models::contract::Contract;
+use crate::models::order::Order;
+
+// Error codes that mean an order was not accepted: from TWS duplicate order id, price that
+// does not fit the minimum tick, no security definition, order rejected, security not
+// available and request validation failure, from the client an outdated TWS and a failed send.
+const REJECT_CODES: [i32; 8] = [103, 110, 200, 201, 203, 321, 503, 512];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderUpdate {
+    pub order_id: i32,
+    pub state: OrderLifecycleState,
+    pub filled: f64,
+    pub remaining: f64,
+    pub avg_fill_price: f64,
+    // code and message of an Error event for the order, e.g. a reject or a refused modification
+    pub error: Option<(i32, String)>,
+}
+
+pub struct OrderHandle {
+    pub order_id: i32,
+    pub updates: Receiver<OrderUpdate>,
+}
+
+struct ManagedOrder {
+    contract: Contract,
+    update: OrderUpdate,
+    sender: Sender<OrderUpdate>,
+}
+
+impl ManagedOrder {
+    fn new(order_id: i32, contract: Contract, state: OrderLifecycleState, remaining: f64) -> (Self, OrderHandle) {
+        let (sender, updates) = unbounded();
+        let update = OrderUpdate {
+            order_id,
+            state,
+            filled: 0.0,
+            remaining,
+            avg_fill_price: 0.0,
+            error: None,
+        };
+
+        (ManagedOrder { contract, update, sender }, OrderHandle { order_id, updates })
+    }
+
+    fn publish(&self) {
+        // the caller may have dropped the handle, the order is still tracked
+        self.sender.send(self.update.clone()).unwrap_or_default();
+    }
+}
+
+// Places orders with ids taken from NextValidId and follows each of them through its
+// lifecycle, correlating the OrderStatus, OpenOrder and Error events of the client.
+//
+// The manager does not read the client's events itself: pass every event to on_event from the
+// event loop. Order ids and the request ids of other requests should not overlap, as errors
+// only carry the id.
+#[derive(Default)]
+pub struct OrderManager {
+    next_order_id: Option<i32>,
+    orders: HashMap<i32, ManagedOrder>,
+}
+
+impl OrderManager {
+    pub fn new() -> Self {
+        OrderManager::default()
+    }
+
+    // None until the first NextValidId arrived, which TWS sends on connection and in answer
+    // to req_ids.
+    pub fn next_order_id(&self) -> Option<i32> {
+        self.next_order_id
+    }
+
+    pub fn place(&mut self, client: &mut IbClient, contract: Contract, order: Order) -> Result<OrderHandle, Box<dyn Error>> {
+        let order_id = self.allocate_order_ids(client, 1)?;
+
+        Ok(self.submit(client, order_id, contract, order))
+    }
+
+    // Places orders that belong together, e.g. the legs of a bracket, under consecutive ids.
+    // A parent_id that refers to the order_id of another order of the group is rewritten to
+    // the id that order is placed under, so the legs stay attached to their parent.
+    pub fn place_group(&mut self, client: &mut IbClient, contract: Contract, orders: Vec<Order>) -> Result<Vec<OrderHandle>, Box<dyn Error>> {
+        let first_order_id = self.allocate_order_ids(client, orders.len() as i32)?;
+
+        let handles = link_group(first_order_id, orders).into_iter()
+            .map(|order| self.submit(client, order.order_id, contract.clone(), order))
+            .collect();
+
+        Ok(handles)
+    }
+
+    // Sends the order again under the id of a working order, e.g. with a new limit price or
+    // quantity. The contract can not change.
+    pub fn modify(&mut self, client: &mut IbClient, order_id: i32, mut order: Order) -> Result<(), Box<dyn Error>> {
+        if !client.is_connected {
+            return Err(Box::new(io::Error::new(io::ErrorKind::NotConnected, "Not connected")));
+        }
+
+        let managed_order = self.working_order(order_id)?;
+        let contract = managed_order.contract.clone();
+
+        order.order_id = order_id;
+        client.place_order(order_id, contract, order);
+
+        // the order keeps working with its previous parameters, on_event still gets the error
+        match send_error(client, order_id) {
+            Some((code, message)) => Err(Box::new(io::Error::other(format!("Order {} was not modified: {} {}", order_id, code, message)))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn cancel(&mut self, client: &mut IbClient, order_id: i32) -> Result<(), Box<dyn Error>> {
+        self.working_order(order_id)?;
+        client.req_cancel_order(order_id, order_id);
+
+        Ok(())
+    }
+
+    pub fn state(&self, order_id: i32) -> Option<OrderLifecycleState> {
+        self.orders.get(&order_id).map(|managed_order| managed_order.update.state)
+    }
+
+    // Ids of the orders that are not in a final state, in no particular order.
+    pub fn working_orders(&self) -> Vec<i32> {
+        self.orders.iter()
+            .filter(|(_, managed_order)| !managed_order.update.state.is_final())
+            .map(|(order_id, _)| *order_id)
+            .collect()
+    }
+
+    // A new handle for an order the manager knows about, e.g. one rebuilt from OpenOrder after
+    // a restart. The previous handle of the order stops receiving updates.
+    pub fn handle(&mut self, order_id: i32) -> Option<OrderHandle> {
+        let managed_order = self.orders.get_mut(&order_id)?;
+        let (sender, updates) = unbounded();
+        managed_order.sender = sender;
+        managed_order.publish();
+
+        Some(OrderHandle { order_id, updates })
+    }
+
+    pub fn on_event(&mut self, event: &IncomingMessagesEnum) {
+        match event {
+            IncomingMessagesEnum::NextValidId(order_id) => {
+                self.next_order_id = Some(self.next_order_id.map_or(*order_id, |next_order_id| next_order_id.max(*order_id)));
+            },
+            IncomingMessagesEnum::OrderStatus(status) => {
+                let managed_order = match self.orders.get_mut(&status.order_id) {
+                    Some(managed_order) => managed_order,
+                    None => return,
+                };
+
+                if managed_order.update.state.is_final() {
+                    return;
+                }
+
+                let state = OrderLifecycleState::from_status(status.status.as_str(), status.filled)
+                    .unwrap_or(managed_order.update.state);
+                let update = OrderUpdate {
+                    order_id: status.order_id,
+                    state,
+                    filled: status.filled,
+                    remaining: status.remaining,
+                    avg_fill_price: status.avg_fill_price,
+                    error: None,
+                };
+
+                // TWS repeats unchanged statuses, e.g. with an OpenOrder event
+                if update != managed_order.update {
+                    managed_order.update = update;
+                    managed_order.publish();
+                }
+            },
+            IncomingMessagesEnum::OpenOrder(item) => {
+                // orders placed in TWS that were not bound to the api have no order id
+                if item.order_id == 0 || self.orders.contains_key(&item.order_id) {
+                    return;
+                }
+
+                let state = OrderLifecycleState::from_status(item.order_state.status.as_str(), 0.0)
+                    .unwrap_or(OrderLifecycleState::Submitted);
+                let (managed_order, _) = ManagedOrder::new(item.order_id, item.contract.clone(), state, item.order.total_quantity);
+                self.orders.insert(item.order_id, managed_order);
+
+                if self.next_order_id.is_some_and(|next_order_id| next_order_id <= item.order_id) {
+                    self.next_order_id = Some(item.order_id + 1);
+                }
+            },
+            IncomingMessagesEnum::Error(order_id, code, message) => {
+                let managed_order = match self.orders.get_mut(order_id) {
+                    Some(managed_order) => managed_order,
+                    None => return,
+                };
+
+                // errors about a working order, e.g. a refused modification, leave it as it is
+                if managed_order.update.state == OrderLifecycleState::PendingSubmit && REJECT_CODES.contains(code) {
+                    managed_order.update.state = OrderLifecycleState::Rejected;
+                }
+                managed_order.update.error = Some((*code, message.clone()));
+                managed_order.publish();
+            },
+            _ => {}
+        }
+    }
+
+    fn allocate_order_ids(&mut self, client: &IbClient, count: i32) -> Result<i32, Box<dyn Error>> {
+        if !client.is_connected {
+            return Err(Box::new(io::Error::new(io::ErrorKind::NotConnected, "Not connected")));
+        }
+
+        let order_id = self.next_order_id
+            .ok_or_else(|| io::Error::other("No valid order id received yet, wait for NextValidId"))?;
+        self.next_order_id = Some(order_id + count);
+
+        Ok(order_id)
+    }
+
+    // A failed send is reported through an Error event for the order, on_event rejects it.
+    fn submit(&mut self, client: &mut IbClient, order_id: i32, contract: Contract, mut order: Order) -> OrderHandle {
+        let (managed_order, handle) = ManagedOrder::new(order_id, contract.clone(), OrderLifecycleState::PendingSubmit, order.total_quantity);
+        managed_order.publish();
+        self.orders.insert(order_id, managed_order);
+
+        order.order_id = order_id;
+        client.place_order(order_id, contract, order);
+
+        handle
+    }
+
+    fn working_order(&self, order_id: i32) -> Result<&ManagedOrder, Box<dyn Error>> {
+        let managed_order = self.orders.get(&order_id)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("Unknown order id {}", order_id)))?;
+
+        if managed_order.update.state.is_final() {
+            return Err(Box::new(io::Error::new(io::ErrorKind::InvalidInput, format!("Order {} is already {:?}", order_id, managed_order.update.state))));
+        }
+
+        Ok(managed_order)
+    }
+}
+
+// Numbers the orders from first_order_id on and points each parent_id that refers to an order
+// of the group at that order's new id.
+fn link_group(first_order_id: i32, mut orders: Vec<Order>) -> Vec<Order> {
+    let order_ids: HashMap<i32, i32> = orders.iter()
+        .enumerate()
+        .filter(|(_, order)| order.order_id != 0)
+        .map(|(index, order)| (order.order_id, first_order_id + index as i32))
+        .collect();
+
+    for (index, order) in orders.iter_mut().enumerate() {
+        order.order_id = first_order_id + index as i32;
+        if let Some(parent_id) = order_ids.get(&order.parent_id) {
+            order.parent_id = *parent_id;
+        }
+    }
+
+    orders
+}
+
+// The client reports validation and send failures as events right away, look for one about
+// the order and queue every event again in its order.
+fn send_error(client: &mut IbClient, order_id: i32) -> Option<(i32, String)> {
+    let events: Vec<IncomingMessagesEnum> = client.event_receiver.try_iter().collect();
+    let error = events.iter().find_map(|event| match event {
+        IncomingMessagesEnum::Error(id, code, message) if *id == order_id && REJECT_CODES.contains(code) => Some((*code, message.clone())),
+        _ => None,
+    });
+
+    for event in events {
+        client.event_sender.send(event).unwrap_or_default();
+    }
+
+    error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::order_status_message::OrderStatusMessage;
+    use crate::models::order_data_item::OrderDataItem;
+    use crate::models::order_state::OrderState;
+
+    // connected as far as the checks go, sends fail as there is no socket
+    fn client() -> IbClient {
+        let mut client = IbClient::new("127.0.0.1".to_string(), 7497, 0);
+        client.is_connected = true;
+        client.client_socket.set_server_version(151);
+        client
+    }
+
+    fn order(total_quantity: f64) -> Order {
+        let mut order = Order::new();
+        order.action = "BUY".to_string();
+        order.order_type = "LMT".to_string();
+        order.lmt_price = 124.0;
+        order.total_quantity = total_quantity;
+        order
+    }
+
+    fn status(order_id: i32, status: &str, filled: f64, remaining: f64) -> IncomingMessagesEnum {
+        let mut message = OrderStatusMessage::new();
+        message.order_id = order_id;
+        message.status = status.to_string();
+        message.filled = filled;
+        message.remaining = remaining;
+        IncomingMessagesEnum::OrderStatus(message)
+    }
+
+    fn states(handle: &OrderHandle) -> Vec<OrderLifecycleState> {
+        handle.updates.try_iter().map(|update| update.state).collect()
+    }
+
+    #[test]
+    fn order_ids_come_from_next_valid_id() {
+        let mut client = client();
+        let mut manager = OrderManager::new();
+        assert!(manager.place(&mut client, Contract::new(), order(100.0)).is_err());
+
+        manager.on_event(&IncomingMessagesEnum::NextValidId(10));
+        let first = manager.place(&mut client, Contract::new(), order(100.0)).unwrap();
+        manager.on_event(&IncomingMessagesEnum::NextValidId(5));
+        let second = manager.place(&mut client, Contract::new(), order(100.0)).unwrap();
+
+        assert_eq!((first.order_id, second.order_id), (10, 11));
+        assert_eq!(manager.next_order_id(), Some(12));
+    }
+
+    #[test]
+    fn orders_are_not_placed_while_disconnected() {
+        let mut client = IbClient::new("127.0.0.1".to_string(), 7497, 0);
+        let mut manager = OrderManager::new();
+        manager.on_event(&IncomingMessagesEnum::NextValidId(10));
+
+        assert!(manager.place(&mut client, Contract::new(), order(100.0)).is_err());
+        assert_eq!(manager.next_order_id(), Some(10));
+        assert!(manager.working_orders().is_empty());
+    }
+
+    #[test]
+    fn failed_sends_reject_the_order() {
+        let mut client = client();
+        let mut manager = OrderManager::new();
+        manager.on_event(&IncomingMessagesEnum::NextValidId(1));
+        let handle = manager.place(&mut client, Contract::new(), order(100.0)).unwrap();
+
+        for event in client.event_receiver.try_iter() {
+            manager.on_event(&event);
+        }
+
+        let update = handle.updates.try_iter().last().unwrap();
+        assert_eq!(update.state, OrderLifecycleState::Rejected);
+        assert_eq!(update.error.unwrap().0, 512);
+        assert!(manager.working_orders().is_empty());
+    }
+
+    #[test]
+    fn modifications_are_not_sent_while_disconnected_and_report_failed_sends() {
+        let mut client = client();
+        let mut manager = OrderManager::new();
+        manager.on_event(&IncomingMessagesEnum::NextValidId(1));
+        manager.place(&mut client, Contract::new(), order(100.0)).unwrap();
+        manager.on_event(&status(1, "Submitted", 0.0, 100.0));
+        client.event_receiver.try_iter().for_each(drop);
+
+        let error = manager.modify(&mut client, 1, order(50.0)).unwrap_err();
+        assert!(error.to_string().contains("512"));
+        // the failed send is still queued for on_event
+        assert!(client.event_receiver.try_iter().any(|event| matches!(event, IncomingMessagesEnum::Error(1, 512, _))));
+
+        client.is_connected = false;
+        let error = manager.modify(&mut client, 1, order(50.0)).unwrap_err();
+        assert_eq!(error.downcast_ref::<io::Error>().unwrap().kind(), io::ErrorKind::NotConnected);
+        assert!(client.event_receiver.try_iter().next().is_none());
+    }
+
+    #[test]
+    fn grouped_orders_keep_their_parent_links() {
+        let orders = link_group(40, Order::bracket(1, "BUY", 100.0, 185.0, 195.0, 180.0).unwrap());
+
+        let ids: Vec<(i32, i32)> = orders.iter().map(|order| (order.order_id, order.parent_id)).collect();
+        assert_eq!(ids, vec![(40, 0), (41, 40), (42, 40)]);
+
+        let mut client = client();
+        let mut manager = OrderManager::new();
+        manager.on_event(&IncomingMessagesEnum::NextValidId(40));
+        let handles = manager.place_group(&mut client, Contract::new(), Order::bracket(1, "BUY", 100.0, 185.0, 195.0, 180.0).unwrap()).unwrap();
+
+        assert_eq!(handles.iter().map(|handle| handle.order_id).collect::<Vec<_>>(), vec![40, 41, 42]);
+        assert_eq!(manager.next_order_id(), Some(43));
+    }
+
+    #[test]
+    fn order_goes_through_its_lifecycle() {
+        let mut client = client();
+        let mut manager = OrderManager::new();
+        manager.on_event(&IncomingMessagesEnum::NextValidId(1));
+        let handle = manager.place(&mut client, Contract::new(), order(100.0)).unwrap();
+
+        manager.on_event(&status(1, "PreSubmitted", 0.0, 100.0));
+        manager.on_event(&status(1, "Submitted", 0.0, 100.0));
+        manager.on_event(&status(1, "Submitted", 0.0, 100.0));
+        manager.on_event(&status(1, "Submitted", 40.0, 60.0));
+        manager.on_event(&status(1, "Filled", 100.0, 0.0));
+        manager.on_event(&status(1, "Submitted", 100.0, 0.0));
+
+        assert_eq!(states(&handle), vec![
+            OrderLifecycleState::PendingSubmit,
+            OrderLifecycleState::Submitted,
+            OrderLifecycleState::PartiallyFilled,
+            OrderLifecycleState::Filled,
+        ]);
+        assert!(manager.working_orders().is_empty());
+        assert!(manager.cancel(&mut client, 1).is_err());
+    }
+
+    #[test]
+    fn rejects_only_apply_to_orders_not_accepted_yet() {
+        let mut client = client();
+        let mut manager = OrderManager::new();
+        manager.on_event(&IncomingMessagesEnum::NextValidId(1));
+        let rejected = manager.place(&mut client, Contract::new(), order(100.0)).unwrap();
+        let working = manager.place(&mut client, Contract::new(), order(100.0)).unwrap();
+
+        manager.on_event(&IncomingMessagesEnum::Error(1, 201, "Order rejected - reason: insufficient margin".to_string()));
+        manager.on_event(&status(2, "Submitted", 0.0, 100.0));
+        manager.on_event(&IncomingMessagesEnum::Error(2, 110, "The price does not conform to the minimum price variation".to_string()));
+
+        let update = rejected.updates.try_iter().last().unwrap();
+        assert_eq!(update.state, OrderLifecycleState::Rejected);
+        assert_eq!(update.error.unwrap().0, 201);
+
+        let update = working.updates.try_iter().last().unwrap();
+        assert_eq!(update.state, OrderLifecycleState::Submitted);
+        assert_eq!(update.error.unwrap().0, 110);
+        assert_eq!(manager.working_orders(), vec![2]);
+    }
+
+    #[test]
+    fn open_orders_are_adopted_after_a_restart() {
+        let mut manager = OrderManager::new();
+        manager.on_event(&IncomingMessagesEnum::NextValidId(1));
+
+        let mut order_state = OrderState::new();
+        order_state.status = "Submitted".to_string();
+        let mut order = order(100.0);
+        order.order_id = 7;
+        manager.on_event(&IncomingMessagesEnum::OpenOrder(OrderDataItem::new(7, order, Contract::new(), order_state)));
+
+        assert_eq!(manager.state(7), Some(OrderLifecycleState::Submitted));
+        assert_eq!(manager.next_order_id(), Some(8));
+
+        let handle = manager.handle(7).unwrap();
+        manager.on_event(&status(7, "Cancelled", 0.0, 100.0));
+        assert_eq!(states(&handle), vec![OrderLifecycleState::Submitted, OrderLifecycleState::Cancelled]);
+    }
+}